@@ -0,0 +1,533 @@
+#![allow(unused)]
+
+// tipos abstratos de dados (ADTs): o que uma estrutura promete fazer, sem dizer como.
+// uma pilha pode ser um `Vector` ou uma `LinkedList`; um algoritmo que só precisa de uma
+// pilha pode receber qualquer `S: Stack<T>` e funcionar com as duas.
+
+use crate::{
+    allocator::RawAlloc, arena_list::ArenaList, deque, doubly_linked_list::DoublyLinkedList,
+    linked_list::LinkedList, small_vector::SmallVector, unrolled_list::UnrolledList,
+    vector::Vector,
+};
+
+/// Qualquer coleção que sabe quantos elementos guarda. Base das outras traits.
+pub trait Collection {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Pilha (LIFO): o último elemento a entrar é o primeiro a sair.
+pub trait Stack<T>: Collection {
+    fn push(&mut self, element: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    /// Elemento do topo, o próximo a sair.
+    fn peek(&self) -> Option<&T>;
+}
+
+/// Fila (FIFO): os elementos saem na mesma ordem em que entraram.
+pub trait Queue<T>: Collection {
+    /// Coloca `element` no final da fila.
+    fn enqueue(&mut self, element: T);
+
+    /// Tira o elemento do começo da fila.
+    fn dequeue(&mut self) -> Option<T>;
+
+    /// Elemento do começo da fila, o próximo a sair.
+    fn front(&self) -> Option<&T>;
+}
+
+/// Fila de duas pontas: dá pra colocar e tirar tanto do começo quanto do final.
+pub trait Deque<T>: Collection {
+    fn push_front(&mut self, element: T);
+
+    fn push_back(&mut self, element: T);
+
+    fn pop_front(&mut self) -> Option<T>;
+
+    fn pop_back(&mut self) -> Option<T>;
+
+    fn front(&self) -> Option<&T>;
+
+    fn back(&self) -> Option<&T>;
+}
+
+/// Sequência com acesso por índice, onde dá pra inserir e remover em qualquer posição.
+pub trait Sequence<T>: Collection {
+    fn get(&self, index: usize) -> Option<&T>;
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T>;
+
+    /// Insere em `index`, empurrando o resto pra frente. Entra em pânico se `index > len`.
+    fn insert(&mut self, index: usize, element: T);
+
+    /// Remove o elemento em `index`. Entra em pânico se `index >= len`.
+    fn remove(&mut self, index: usize) -> T;
+}
+
+// os métodos próprios de cada tipo têm prioridade sobre os das traits, então nos impls abaixo
+// `self.push(...)` e afins chamam a implementação concreta, e não a trait de novo. a exceção é
+// o `get` do `Vector`, que vem do slice: lá chamamos o do slice explicitamente.
+
+impl<T, A: RawAlloc> Collection for Vector<T, A> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, A: RawAlloc> Stack<T> for Vector<T, A> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+impl<T, A: RawAlloc> Sequence<T> for Vector<T, A> {
+    fn get(&self, index: usize) -> Option<&T> {
+        <[T]>::get(self, index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        <[T]>::get_mut(self, index)
+    }
+
+    fn insert(&mut self, index: usize, element: T) {
+        self.insert(index, element);
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        self.remove(index)
+    }
+}
+
+impl<T> Collection for LinkedList<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Stack<T> for LinkedList<T> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+}
+
+impl<T, const N: usize> Collection for SmallVector<T, N> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const N: usize> Stack<T> for SmallVector<T, N> {
+    fn push(&mut self, element: T) {
+        self.push(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+impl<T, A: RawAlloc> Collection for deque::Deque<T, A> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+// o topo da pilha é o final do deque.
+impl<T, A: RawAlloc> Stack<T> for deque::Deque<T, A> {
+    fn push(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T, A: RawAlloc> Queue<T> for deque::Deque<T, A> {
+    fn enqueue(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+}
+
+impl<T, A: RawAlloc> Deque<T> for deque::Deque<T, A> {
+    fn push_front(&mut self, element: T) {
+        self.push_front(element);
+    }
+
+    fn push_back(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn back(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T> Collection for DoublyLinkedList<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Stack<T> for DoublyLinkedList<T> {
+    fn push(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T> Queue<T> for DoublyLinkedList<T> {
+    fn enqueue(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+}
+
+impl<T> Deque<T> for DoublyLinkedList<T> {
+    fn push_front(&mut self, element: T) {
+        self.push_front(element);
+    }
+
+    fn push_back(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn back(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T> Collection for ArenaList<T> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+// as inserções da `ArenaList` devolvem um handle; aqui ele simplesmente não é usado.
+impl<T> Queue<T> for ArenaList<T> {
+    fn enqueue(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+}
+
+impl<T> Deque<T> for ArenaList<T> {
+    fn push_front(&mut self, element: T) {
+        self.push_front(element);
+    }
+
+    fn push_back(&mut self, element: T) {
+        self.push_back(element);
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn pop_back(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn front(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn back(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T, const B: usize> Collection for UnrolledList<T, B> {
+    fn len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, const B: usize> Sequence<T> for UnrolledList<T, B> {
+    fn get(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+
+    fn insert(&mut self, index: usize, element: T) {
+        self.insert(index, element);
+    }
+
+    fn remove(&mut self, index: usize) -> T {
+        self.remove(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a suíte de conformidade: cada função testa o contrato de uma trait usando só a trait,
+    // e o macro no final roda ela pra cada tipo que implementa.
+
+    fn stack_conformance<S: Stack<i32> + Default>() {
+        let mut stack = S::default();
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+
+        for i in 0..100 {
+            stack.push(i);
+            assert_eq!(stack.peek(), Some(&i));
+            assert_eq!(stack.len(), i as usize + 1);
+        }
+
+        // LIFO, inclusive intercalando push e pop
+        assert_eq!(stack.pop(), Some(99));
+        stack.push(-1);
+        assert_eq!(stack.pop(), Some(-1));
+        for i in (0..99).rev() {
+            assert_eq!(stack.pop(), Some(i));
+        }
+
+        assert!(stack.is_empty());
+        assert_eq!(stack.pop(), None);
+    }
+
+    fn queue_conformance<Q: Queue<i32> + Default>() {
+        let mut queue = Q::default();
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(queue.front(), None);
+
+        for i in 0..100 {
+            queue.enqueue(i);
+            assert_eq!(queue.front(), Some(&0));
+        }
+        assert_eq!(queue.len(), 100);
+
+        // FIFO, inclusive intercalando enqueue e dequeue
+        for i in 0..50 {
+            assert_eq!(queue.dequeue(), Some(i));
+        }
+        queue.enqueue(100);
+        for i in 50..=100 {
+            assert_eq!(queue.front(), Some(&i));
+            assert_eq!(queue.dequeue(), Some(i));
+        }
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.dequeue(), None);
+    }
+
+    fn deque_conformance<D: Deque<i32> + Default>() {
+        let mut deque = D::default();
+        assert!(deque.is_empty());
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!((deque.front(), deque.back()), (None, None));
+
+        for i in 0..50 {
+            deque.push_back(i);
+            deque.push_front(-i);
+        }
+        assert_eq!(deque.len(), 100);
+        assert_eq!(deque.front(), Some(&-49));
+        assert_eq!(deque.back(), Some(&49));
+
+        for i in (0..50).rev() {
+            assert_eq!(deque.pop_front(), Some(-i));
+            assert_eq!(deque.pop_back(), Some(i));
+        }
+
+        // uma ponta só também funciona como pilha
+        deque.push_front(1);
+        deque.push_front(2);
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_back(), Some(1));
+        assert!(deque.is_empty());
+    }
+
+    fn sequence_conformance<S: Sequence<i32> + Default>() {
+        let mut sequence = S::default();
+        assert!(sequence.is_empty());
+        assert_eq!(sequence.get(0), None);
+
+        // monta [0, 1, ..., 99] inserindo em posições variadas
+        for i in (0..100).step_by(2) {
+            sequence.insert(sequence.len(), i);
+        }
+        for i in (1..100).step_by(2) {
+            sequence.insert(i as usize, i);
+        }
+        assert_eq!(sequence.len(), 100);
+        for i in 0..100 {
+            assert_eq!(sequence.get(i), Some(&(i as i32)));
+        }
+        assert_eq!(sequence.get(100), None);
+
+        *sequence.get_mut(10).unwrap() = -10;
+        assert_eq!(sequence.remove(10), -10);
+        assert_eq!(sequence.remove(0), 0);
+        assert_eq!(sequence.remove(sequence.len() - 1), 99);
+        assert_eq!(sequence.len(), 97);
+        assert_eq!(sequence.get(9), Some(&11));
+
+        while !sequence.is_empty() {
+            sequence.remove(sequence.len() / 2);
+        }
+        assert_eq!(sequence.get(0), None);
+    }
+
+    macro_rules! conformance {
+        ($suite:ident { $($name:ident: $ty:ty),* $(,)? }) => {
+            $(
+                #[test]
+                fn $name() {
+                    $suite::<$ty>();
+                }
+            )*
+        };
+    }
+
+    conformance!(stack_conformance {
+        test_vector_is_a_stack: Vector<i32>,
+        test_linked_list_is_a_stack: LinkedList<i32>,
+        test_small_vector_is_a_stack: SmallVector<i32, 8>,
+        test_ring_deque_is_a_stack: deque::Deque<i32>,
+        test_doubly_linked_list_is_a_stack: DoublyLinkedList<i32>,
+    });
+
+    conformance!(queue_conformance {
+        test_ring_deque_is_a_queue: deque::Deque<i32>,
+        test_doubly_linked_list_is_a_queue: DoublyLinkedList<i32>,
+        test_arena_list_is_a_queue: ArenaList<i32>,
+    });
+
+    conformance!(deque_conformance {
+        test_ring_deque_is_a_deque: deque::Deque<i32>,
+        test_doubly_linked_list_is_a_deque: DoublyLinkedList<i32>,
+        test_arena_list_is_a_deque: ArenaList<i32>,
+    });
+
+    conformance!(sequence_conformance {
+        test_vector_is_a_sequence: Vector<i32>,
+        test_unrolled_list_is_a_sequence: UnrolledList<i32, 4>,
+    });
+
+    // exemplo de algoritmo genérico: funciona com qualquer pilha.
+    fn balanced<S: Stack<char> + Default>(text: &str) -> bool {
+        let mut open = S::default();
+
+        for c in text.chars() {
+            match c {
+                '(' | '[' | '{' => open.push(c),
+                ')' | ']' | '}' => {
+                    let expected = match c {
+                        ')' => '(',
+                        ']' => '[',
+                        _ => '{',
+                    };
+                    if open.pop() != Some(expected) {
+                        return false;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        open.is_empty()
+    }
+
+    #[test]
+    fn test_generic_algorithm() {
+        for text in ["", "()", "([]{()})", "a(b)c"] {
+            assert!(balanced::<Vector<char>>(text));
+            assert!(balanced::<LinkedList<char>>(text));
+        }
+        for text in ["(", ")", "(]", "([)]"] {
+            assert!(!balanced::<Vector<char>>(text));
+            assert!(!balanced::<LinkedList<char>>(text));
+        }
+    }
+}
@@ -0,0 +1,344 @@
+#![allow(unused)]
+
+use std::{
+    alloc::{self, Layout},
+    cell::Cell,
+    ptr::{self, NonNull},
+};
+
+/// Interface mínima de um alocador, usada pelas estruturas que guardam seus elementos na HEAP.
+///
+/// A `std` tem a trait `Allocator`, mas ela ainda é instável, então temos a nossa própria
+/// versão bem mais simples.
+///
+/// # Safety
+///
+/// Quem implementa precisa garantir que um bloco devolvido por `allocate` (ou `reallocate`)
+/// continua válido, com o tamanho e alinhamento pedidos, até ser passado pra `deallocate`
+/// ou `reallocate`.
+pub unsafe trait RawAlloc {
+    /// Aloca um bloco para `layout`. Retorna `None` se não houver memória.
+    /// `layout.size()` nunca é zero.
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+    /// Libera um bloco.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` precisa ter sido alocado por esse alocador com esse mesmo `layout`.
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+    /// Muda o tamanho de um bloco, preservando o conteúdo até o menor dos dois tamanhos.
+    /// Se falhar, o bloco antigo continua válido.
+    ///
+    /// # Safety
+    ///
+    /// Mesmas condições de `deallocate`, e `new_layout` precisa ter o mesmo alinhamento.
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        // implementação genérica: aloca um bloco novo, copia e libera o antigo.
+        // alocadores que conseguem crescer "no lugar" podem sobrescrever isso.
+        let new_ptr = self.allocate(new_layout)?;
+        let size = old_layout.size().min(new_layout.size());
+
+        unsafe {
+            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), size);
+            self.deallocate(ptr, old_layout);
+        }
+
+        Some(new_ptr)
+    }
+}
+
+// permite passar uma referência pro alocador (`Vector<T, &Bump>`), assim vários vetores podem
+// dividir a mesma arena.
+unsafe impl<A: RawAlloc + ?Sized> RawAlloc for &A {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        unsafe { (**self).reallocate(ptr, old_layout, new_layout) }
+    }
+}
+
+/// O alocador global do programa (malloc / realloc / free por baixo dos panos).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Global;
+
+unsafe impl RawAlloc for Global {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { alloc::alloc(layout) })
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { alloc::dealloc(ptr.as_ptr(), layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        NonNull::new(unsafe { alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) })
+    }
+}
+
+/// Alocador de arena (bump allocator).
+///
+/// Reserva um bloco de tamanho fixo e vai "empurrando" um offset a cada alocação.
+/// Liberar memória é praticamente de graça: tudo volta pro sistema quando a arena é dropada.
+pub struct Bump {
+    start: NonNull<u8>,
+    size: usize,
+    // `Cell` porque as alocações recebem `&self`: a arena é compartilhada entre vetores.
+    offset: Cell<usize>,
+    // offset onde começa a última alocação, pra conseguirmos crescer ou liberar ela no lugar.
+    last: Cell<usize>,
+}
+
+impl Bump {
+    // alinhamento do bloco inteiro. alocações com alinhamento maior ainda funcionam,
+    // só que podem desperdiçar alguns bytes de padding.
+    const ALIGN: usize = 16;
+
+    pub fn with_capacity(size: usize) -> Self {
+        let start = match size {
+            0 => NonNull::dangling(),
+            _ => {
+                let layout =
+                    Layout::from_size_align(size, Self::ALIGN).expect("Invalid arena size");
+                NonNull::new(unsafe { alloc::alloc(layout) }).expect("Memory allocation failed")
+            }
+        };
+
+        Self {
+            start,
+            size,
+            offset: Cell::new(0),
+            last: Cell::new(0),
+        }
+    }
+
+    /// Quantos bytes da arena já foram usados (incluindo padding).
+    pub fn used(&self) -> usize {
+        self.offset.get()
+    }
+
+    pub const fn capacity(&self) -> usize {
+        self.size
+    }
+
+    fn is_last(&self, ptr: NonNull<u8>) -> bool {
+        ptr.as_ptr() as usize == self.start.as_ptr() as usize + self.last.get()
+    }
+}
+
+unsafe impl RawAlloc for Bump {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let base = self.start.as_ptr() as usize;
+        // arredonda o endereço pra cima até o próximo múltiplo do alinhamento.
+        // como o alinhamento é sempre potência de 2, dá pra fazer isso com máscara de bits.
+        let address =
+            (base + self.offset.get()).checked_add(layout.align() - 1)? & !(layout.align() - 1);
+        let begin = address - base;
+        let end = begin.checked_add(layout.size())?;
+
+        if end > self.size {
+            return None;
+        }
+
+        self.last.set(begin);
+        self.offset.set(end);
+
+        // `wrapping_add` mantém a proveniência do ponteiro original da arena.
+        NonNull::new(self.start.as_ptr().wrapping_add(begin))
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // só conseguimos devolver a memória se ela foi a última alocação.
+        // o resto fica "vazando" até a arena morrer, e tudo bem, é o preço da simplicidade.
+        if self.is_last(ptr) {
+            self.offset.set(self.last.get());
+        }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        // se o bloco é o último da arena, dá pra só mover o offset sem copiar nada.
+        if self.is_last(ptr) {
+            let end = self.last.get().checked_add(new_layout.size())?;
+
+            if end <= self.size {
+                self.offset.set(end);
+                return Some(ptr);
+            }
+
+            return None;
+        }
+
+        let new_ptr = self.allocate(new_layout)?;
+        let size = old_layout.size().min(new_layout.size());
+        unsafe { ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr(), size) };
+
+        Some(new_ptr)
+    }
+}
+
+impl Drop for Bump {
+    fn drop(&mut self) {
+        if self.size != 0 {
+            let layout = Layout::from_size_align(self.size, Self::ALIGN).unwrap();
+            unsafe { alloc::dealloc(self.start.as_ptr(), layout) }
+        }
+    }
+}
+
+/// Alocador que só repassa as chamadas pra outro, contando quantas vezes cada operação acontece.
+/// Útil nos testes pra verificar que não estamos vazando memória ou realocando à toa.
+#[derive(Debug, Default)]
+pub struct Counting<A: RawAlloc = Global> {
+    inner: A,
+    allocations: Cell<usize>,
+    reallocations: Cell<usize>,
+    deallocations: Cell<usize>,
+}
+
+impl<A: RawAlloc> Counting<A> {
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            allocations: Cell::new(0),
+            reallocations: Cell::new(0),
+            deallocations: Cell::new(0),
+        }
+    }
+
+    pub fn allocations(&self) -> usize {
+        self.allocations.get()
+    }
+
+    pub fn reallocations(&self) -> usize {
+        self.reallocations.get()
+    }
+
+    pub fn deallocations(&self) -> usize {
+        self.deallocations.get()
+    }
+
+    /// Blocos alocados que ainda não foram liberados.
+    pub fn live(&self) -> usize {
+        self.allocations() - self.deallocations()
+    }
+}
+
+unsafe impl<A: RawAlloc> RawAlloc for Counting<A> {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let ptr = self.inner.allocate(layout)?;
+        self.allocations.set(self.allocations.get() + 1);
+
+        Some(ptr)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.deallocations.set(self.deallocations.get() + 1);
+        unsafe { self.inner.deallocate(ptr, layout) }
+    }
+
+    unsafe fn reallocate(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Option<NonNull<u8>> {
+        let new_ptr = unsafe { self.inner.reallocate(ptr, old_layout, new_layout)? };
+        self.reallocations.set(self.reallocations.get() + 1);
+
+        Some(new_ptr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bump_alignment() {
+        let arena = Bump::with_capacity(64);
+
+        let a = arena.allocate(Layout::new::<u8>()).unwrap();
+        let b = arena.allocate(Layout::new::<u64>()).unwrap();
+
+        assert_eq!(b.as_ptr() as usize % 8, 0);
+        assert!(b.as_ptr() as usize > a.as_ptr() as usize);
+        // 1 byte + 7 de padding + 8 do u64
+        assert_eq!(arena.used(), 16);
+    }
+
+    #[test]
+    fn test_bump_out_of_memory() {
+        let arena = Bump::with_capacity(16);
+
+        assert!(arena.allocate(Layout::new::<[u8; 16]>()).is_some());
+        assert!(arena.allocate(Layout::new::<u8>()).is_none());
+    }
+
+    #[test]
+    fn test_bump_grows_last_allocation_in_place() {
+        let arena = Bump::with_capacity(64);
+        let old = Layout::array::<u32>(2).unwrap();
+        let new = Layout::array::<u32>(8).unwrap();
+
+        unsafe {
+            let ptr = arena.allocate(old).unwrap();
+            ptr.as_ptr().cast::<u32>().write(7);
+
+            let grown = arena.reallocate(ptr, old, new).unwrap();
+            assert_eq!(grown, ptr);
+            assert_eq!(grown.as_ptr().cast::<u32>().read(), 7);
+            assert_eq!(arena.used(), 32);
+
+            arena.deallocate(grown, new);
+            assert_eq!(arena.used(), 0);
+        }
+    }
+
+    #[test]
+    fn test_counting() {
+        let counting = Counting::new(Global);
+        let layout = Layout::new::<u64>();
+
+        unsafe {
+            let ptr = counting.allocate(layout).unwrap();
+            let ptr = counting
+                .reallocate(ptr, layout, Layout::new::<[u64; 2]>())
+                .unwrap();
+            assert_eq!(counting.live(), 1);
+
+            counting.deallocate(ptr, Layout::new::<[u64; 2]>());
+        }
+
+        assert_eq!(counting.allocations(), 1);
+        assert_eq!(counting.reallocations(), 1);
+        assert_eq!(counting.deallocations(), 1);
+        assert_eq!(counting.live(), 0);
+    }
+}
@@ -0,0 +1,707 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::fmt;
+
+/// Lista duplamente encadeada cujos nodes moram todos num [`Vector`].
+///
+/// Em vez de ponteiros, os nodes se ligam pelo índice (`u32`) um do outro no vetor. Isso deixa
+/// os nodes próximos na memória (bom pro cache), dispensa `unsafe` e faz a lista inteira ser
+/// só um vetor, fácil de copiar ou serializar. Posições liberadas por remoções vão pra uma
+/// free list e são reaproveitadas nas próximas inserções.
+///
+/// Cada inserção devolve um [`Handle`], que continua apontando pro mesmo elemento não importa
+/// o que aconteça com o resto da lista, e permite remover ou mover o elemento em O(1). É o que
+/// torna ela útil como base de caches LRU: um mapa guarda o handle de cada chave.
+pub(crate) struct ArenaList<T> {
+    slots: Vector<Slot<T>>,
+    head: u32,
+    tail: u32,
+    // primeira posição livre; as outras ficam encadeadas pelo `next` de cada uma.
+    free: u32,
+    // geração com que as posições novas começam. sobe no `clear` e no `compact`, que jogam
+    // posições fora e, com elas, a geração que elas tinham.
+    base_generation: u32,
+    length: usize,
+}
+
+struct Slot<T> {
+    // `None` quando a posição está na free list.
+    element: Option<T>,
+    prev: u32,
+    next: u32,
+    // incrementa toda vez que o elemento da posição sai. um handle guarda a geração de quando
+    // foi criado, então um handle antigo nunca confunde o elemento novo que reusou a posição
+    // com o dele.
+    generation: u32,
+}
+
+// índice que não aponta pra lugar nenhum, como o `None` de um `Option<Box<Node>>`.
+const NIL: u32 = u32::MAX;
+
+/// Referência estável pra um elemento da [`ArenaList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct Handle {
+    index: u32,
+    generation: u32,
+}
+
+impl<T> ArenaList<T> {
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: Vector::with_capacity(capacity),
+            head: NIL,
+            tail: NIL,
+            free: NIL,
+            base_generation: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Quantidade de posições no vetor, contando as livres.
+    pub(crate) fn slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub(crate) fn push_front(&mut self, element: T) -> Handle {
+        self.link_new(element, NIL, self.head)
+    }
+
+    pub(crate) fn push_back(&mut self, element: T) -> Handle {
+        self.link_new(element, self.tail, NIL)
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        self.unlink(self.head)
+    }
+
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        self.unlink(self.tail)
+    }
+
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.element(self.head)
+    }
+
+    pub(crate) fn back(&self) -> Option<&T> {
+        self.element(self.tail)
+    }
+
+    pub(crate) fn front_handle(&self) -> Option<Handle> {
+        self.handle_at(self.head)
+    }
+
+    pub(crate) fn back_handle(&self) -> Option<Handle> {
+        self.handle_at(self.tail)
+    }
+
+    /// Se o handle ainda aponta pra um elemento da lista.
+    pub(crate) fn contains(&self, handle: Handle) -> bool {
+        self.resolve(handle).is_some()
+    }
+
+    pub(crate) fn get(&self, handle: Handle) -> Option<&T> {
+        self.element(self.resolve(handle)?)
+    }
+
+    pub(crate) fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        let index = self.resolve(handle)?;
+        self.slots[index as usize].element.as_mut()
+    }
+
+    /// Handle do elemento seguinte, ou `None` se `handle` for o último (ou inválido).
+    pub(crate) fn next(&self, handle: Handle) -> Option<Handle> {
+        let index = self.resolve(handle)?;
+        self.handle_at(self.slots[index as usize].next)
+    }
+
+    pub(crate) fn prev(&self, handle: Handle) -> Option<Handle> {
+        let index = self.resolve(handle)?;
+        self.handle_at(self.slots[index as usize].prev)
+    }
+
+    /// Insere `element` logo depois do elemento de `handle`.
+    pub(crate) fn insert_after(&mut self, handle: Handle, element: T) -> Handle {
+        let index = self.expect_valid(handle);
+        let next = self.slots[index as usize].next;
+        self.link_new(element, index, next)
+    }
+
+    /// Insere `element` logo antes do elemento de `handle`.
+    pub(crate) fn insert_before(&mut self, handle: Handle, element: T) -> Handle {
+        let index = self.expect_valid(handle);
+        let prev = self.slots[index as usize].prev;
+        self.link_new(element, prev, index)
+    }
+
+    /// Remove o elemento de `handle` em O(1). Devolve `None` se ele já tinha sido removido.
+    pub(crate) fn remove(&mut self, handle: Handle) -> Option<T> {
+        let index = self.resolve(handle)?;
+        self.unlink(index)
+    }
+
+    /// Move o elemento pra frente da lista sem mudar o handle dele. É o "acabei de usar"
+    /// de um cache LRU.
+    pub(crate) fn move_to_front(&mut self, handle: Handle) {
+        let index = self.expect_valid(handle);
+        if index != self.head {
+            self.detach(index);
+            self.attach(index, NIL, self.head);
+        }
+    }
+
+    pub(crate) fn move_to_back(&mut self, handle: Handle) {
+        let index = self.expect_valid(handle);
+        if index != self.tail {
+            self.detach(index);
+            self.attach(index, self.tail, NIL);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.retire_generations();
+        self.slots.clear();
+        self.head = NIL;
+        self.tail = NIL;
+        self.free = NIL;
+        self.length = 0;
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: self,
+            front: self.head,
+            back: self.tail,
+            remaining: self.length,
+        }
+    }
+
+    pub(crate) fn compact(&mut self) {
+        self.compact_with(|_, _| {});
+    }
+
+    /// Reescreve o vetor sem posições livres e com os elementos na ordem da lista, liberando
+    /// a memória que sobrar. O(n).
+    ///
+    /// Os elementos mudam de posição, então todos os handles antigos deixam de valer.
+    /// `moved(antigo, novo)` é chamado pra cada elemento, pra quem guarda handles poder
+    /// atualizá-los.
+    pub(crate) fn compact_with<F: FnMut(Handle, Handle)>(&mut self, mut moved: F) {
+        let generation = self.retire_generations();
+        let mut compacted = Vector::with_capacity(self.length);
+        // o handle antigo de cada posição nova. o `moved` só é chamado no final, com a lista
+        // já no lugar: se ele entrar em pânico, a lista continua inteira e consistente.
+        let mut old_handles = Vector::with_capacity(self.length);
+        let mut current = self.head;
+
+        while current != NIL {
+            let slot = &mut self.slots[current as usize];
+            old_handles.push(Handle {
+                index: current,
+                generation: slot.generation,
+            });
+            current = slot.next;
+
+            let index = compacted.len() as u32;
+            compacted.push(Slot {
+                element: slot.element.take(),
+                prev: index.wrapping_sub(1),
+                next: index + 1,
+                generation,
+            });
+        }
+
+        // acertando as pontas: o `prev` do primeiro já deu `NIL` pelo `wrapping_sub`.
+        if let Some(last) = compacted.last_mut() {
+            last.next = NIL;
+        }
+
+        let length = compacted.len() as u32;
+        self.slots = compacted;
+        self.free = NIL;
+        (self.head, self.tail) = match length {
+            0 => (NIL, NIL),
+            _ => (0, length - 1),
+        };
+
+        for (index, old) in old_handles.into_iter().enumerate() {
+            let index = index as u32;
+            moved(old, Handle { index, generation });
+        }
+    }
+
+    // coloca `element` numa posição (reaproveitada da free list, se tiver) e pendura ela entre
+    // `prev` e `next`, que precisam ser vizinhos.
+    fn link_new(&mut self, element: T, prev: u32, next: u32) -> Handle {
+        let index = match self.free {
+            NIL => {
+                let index = self.slots.len();
+                // `NIL` é reservado, então cabem no máximo `u32::MAX` posições.
+                assert!(index < NIL as usize, "arena list capacity exceeded");
+
+                self.slots.push(Slot {
+                    element: Some(element),
+                    prev: NIL,
+                    next: NIL,
+                    generation: self.base_generation,
+                });
+                index as u32
+            }
+            index => {
+                let slot = &mut self.slots[index as usize];
+                self.free = slot.next;
+                slot.element = Some(element);
+                index
+            }
+        };
+
+        self.length += 1;
+        self.attach(index, prev, next);
+
+        Handle {
+            index,
+            generation: self.slots[index as usize].generation,
+        }
+    }
+
+    // tira o elemento de `index` da lista e põe a posição na free list.
+    fn unlink(&mut self, index: u32) -> Option<T> {
+        if index == NIL {
+            return None;
+        }
+
+        self.detach(index);
+        self.length -= 1;
+
+        let slot = &mut self.slots[index as usize];
+        slot.generation = slot.generation.wrapping_add(1);
+        slot.next = self.free;
+        self.free = index;
+
+        slot.element.take()
+    }
+
+    // pendura a posição `index` entre `prev` e `next`.
+    fn attach(&mut self, index: u32, prev: u32, next: u32) {
+        let slot = &mut self.slots[index as usize];
+        slot.prev = prev;
+        slot.next = next;
+
+        match prev {
+            NIL => self.head = index,
+            prev => self.slots[prev as usize].next = index,
+        }
+        match next {
+            NIL => self.tail = index,
+            next => self.slots[next as usize].prev = index,
+        }
+    }
+
+    // costura os vizinhos de `index`, deixando ele solto (mas ainda ocupado).
+    fn detach(&mut self, index: u32) {
+        let Slot { prev, next, .. } = self.slots[index as usize];
+
+        match prev {
+            NIL => self.head = next,
+            prev => self.slots[prev as usize].next = next,
+        }
+        match next {
+            NIL => self.tail = prev,
+            next => self.slots[next as usize].prev = prev,
+        }
+    }
+
+    // escolhe uma geração maior que a de qualquer posição atual e passa a usar ela como base.
+    // assim, depois de jogar posições fora, nenhum handle antigo volta a valer por coincidência.
+    fn retire_generations(&mut self) -> u32 {
+        let newest = self
+            .slots
+            .iter()
+            .map(|slot| slot.generation)
+            .fold(self.base_generation, u32::max);
+
+        self.base_generation = newest.wrapping_add(1);
+        self.base_generation
+    }
+
+    // posição do elemento do handle, se ele ainda estiver na lista.
+    fn resolve(&self, handle: Handle) -> Option<u32> {
+        let slot = self.slots.get(handle.index as usize)?;
+        (slot.generation == handle.generation && slot.element.is_some()).then_some(handle.index)
+    }
+
+    fn expect_valid(&self, handle: Handle) -> u32 {
+        self.resolve(handle)
+            .expect("handle should point to an element of the list")
+    }
+
+    fn element(&self, index: u32) -> Option<&T> {
+        self.slots.get(index as usize)?.element.as_ref()
+    }
+
+    fn handle_at(&self, index: u32) -> Option<Handle> {
+        let slot = self.slots.get(index as usize)?;
+        Some(Handle {
+            index,
+            generation: slot.generation,
+        })
+    }
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for ArenaList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArenaList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for ArenaList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for ArenaList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArenaList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Iterador que empresta os elementos da lista, podendo andar pelas duas pontas.
+pub(crate) struct Iter<'a, T> {
+    list: &'a ArenaList<T>,
+    front: u32,
+    back: u32,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let slot = &self.list.slots[self.front as usize];
+        self.front = slot.next;
+        self.remaining -= 1;
+        slot.element.as_ref()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let slot = &self.list.slots[self.back as usize];
+        self.back = slot.prev;
+        self.remaining -= 1;
+        slot.element.as_ref()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{HashMap, VecDeque};
+
+    fn to_vec<T: Clone>(list: &ArenaList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = ArenaList::new();
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        list.push_front(0);
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&3));
+        assert_eq!(to_vec(&list), [0, 1, 2, 3]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [3, 2, 1, 0]);
+
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut list = ArenaList::new();
+        let a = list.push_back('a');
+        let c = list.push_back('c');
+        let b = list.insert_before(c, 'b');
+        let d = list.insert_after(c, 'd');
+
+        assert_eq!(list.front_handle(), Some(a));
+        assert_eq!(list.back_handle(), Some(d));
+        assert_eq!(list.next(a), Some(b));
+        assert_eq!(list.prev(a), None);
+        assert_eq!(list.prev(d), Some(c));
+
+        *list.get_mut(b).unwrap() = 'B';
+        assert_eq!(list.remove(c), Some('c'));
+        assert_eq!(to_vec(&list), ['a', 'B', 'd']);
+
+        // o handle removido não vale mais, nem quando a posição dele é reaproveitada
+        assert!(!list.contains(c));
+        assert_eq!(list.remove(c), None);
+        let e = list.push_back('e');
+        assert_eq!(list.slots(), 4);
+        assert_eq!(list.get(c), None);
+        assert_eq!(list.get(e), Some(&'e'));
+
+        // e os outros continuam apontando pros mesmos elementos
+        assert_eq!(list.get(a), Some(&'a'));
+        assert_eq!(list.get(b), Some(&'B'));
+        assert_eq!(list.get(d), Some(&'d'));
+    }
+
+    #[test]
+    fn test_move_to_front_and_back() {
+        let mut list = ArenaList::new();
+        let handles: Vec<_> = (0..4).map(|i| list.push_back(i)).collect();
+
+        list.move_to_front(handles[2]);
+        assert_eq!(to_vec(&list), [2, 0, 1, 3]);
+        list.move_to_back(handles[0]);
+        assert_eq!(to_vec(&list), [2, 1, 3, 0]);
+
+        // já estão na ponta: nada muda
+        list.move_to_front(handles[2]);
+        list.move_to_back(handles[0]);
+        assert_eq!(to_vec(&list), [2, 1, 3, 0]);
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), [0, 3, 1, 2]);
+        assert_eq!(list.get(handles[3]), Some(&3));
+    }
+
+    #[test]
+    #[should_panic(expected = "handle should point to an element of the list")]
+    fn test_stale_handle_panics() {
+        let mut list = ArenaList::new();
+        let handle = list.push_back(1);
+        list.remove(handle);
+        list.move_to_front(handle);
+    }
+
+    #[test]
+    fn test_compaction() {
+        let mut list = ArenaList::new();
+        let handles: Vec<_> = (0..10).map(|i| list.push_back(i)).collect();
+        for handle in handles.iter().step_by(2) {
+            list.remove(*handle);
+        }
+        list.move_to_front(handles[9]);
+        assert_eq!(list.slots(), 10);
+
+        let mut remapped = HashMap::new();
+        list.compact_with(|old, new| {
+            remapped.insert(old, new);
+        });
+
+        assert_eq!(list.slots(), 5);
+        assert_eq!(to_vec(&list), [9, 1, 3, 5, 7]);
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            [7, 5, 3, 1, 9]
+        );
+
+        // os handles antigos não valem mais, os novos apontam pros mesmos elementos
+        for (i, old) in handles.iter().enumerate().skip(1).step_by(2) {
+            assert!(!list.contains(*old));
+            assert_eq!(list.get(remapped[old]), Some(&i));
+        }
+
+        // a lista continua funcionando normalmente depois
+        list.push_front(100);
+        list.remove(remapped[&handles[5]]);
+        assert_eq!(to_vec(&list), [100, 9, 1, 3, 7]);
+
+        // mesmo quando a compactação esvazia o vetor, handles antigos não voltam a valer
+        let mut empty: ArenaList<i32> = ArenaList::new();
+        let stale = empty.push_back(1);
+        empty.pop_back();
+        empty.compact();
+        assert_eq!(empty.slots(), 0);
+        empty.push_back(2);
+        assert_eq!(to_vec(&empty), [2]);
+        assert_eq!(empty.get(stale), None);
+
+        let stale = empty.front_handle().unwrap();
+        empty.clear();
+        empty.push_back(3);
+        assert_eq!(empty.get(stale), None);
+    }
+
+    #[test]
+    fn test_compact_panic_keeps_list_consistent() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let mut list = ArenaList::new();
+        let handles: Vec<_> = (0..10).map(|i| list.push_back(i)).collect();
+        for handle in handles.iter().step_by(2) {
+            list.remove(*handle);
+        }
+
+        let mut calls = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.compact_with(|_, _| {
+                calls += 1;
+                assert!(calls < 3, "boom");
+            })
+        }));
+        assert!(result.is_err());
+
+        // a compactação já terminou quando o callback entra em pânico
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.slots(), 5);
+        assert_eq!(to_vec(&list), [1, 3, 5, 7, 9]);
+        list.push_front(0);
+        assert_eq!(to_vec(&list), [0, 1, 3, 5, 7, 9]);
+    }
+
+    #[test]
+    fn test_matches_vec_deque() {
+        let mut list = ArenaList::new();
+        let mut reference = VecDeque::new();
+
+        for i in 0..500 {
+            match i % 5 {
+                0 | 3 => {
+                    list.push_back(i);
+                    reference.push_back(i);
+                }
+                1 => {
+                    list.push_front(i);
+                    reference.push_front(i);
+                }
+                2 => assert_eq!(list.pop_front(), reference.pop_front()),
+                _ => assert_eq!(list.pop_back(), reference.pop_back()),
+            }
+        }
+
+        assert!(list.iter().eq(reference.iter()));
+        // as posições liberadas são reaproveitadas, então o vetor não cresce além do pico
+        assert!(list.slots() <= 200);
+        assert_eq!(format!("{:?}", list.clone()), format!("{reference:?}"));
+    }
+
+    // um cache LRU clássico: o mapa acha o handle da chave e a lista guarda a ordem de uso.
+    // o mais recente fica na frente e o despejado sai de trás.
+    struct LruCache<K, V> {
+        map: HashMap<K, Handle>,
+        order: ArenaList<(K, V)>,
+        capacity: usize,
+    }
+
+    impl<K: std::hash::Hash + Eq + Clone, V> LruCache<K, V> {
+        fn new(capacity: usize) -> Self {
+            Self {
+                map: HashMap::new(),
+                order: ArenaList::with_capacity(capacity),
+                capacity,
+            }
+        }
+
+        fn get(&mut self, key: &K) -> Option<&V> {
+            let handle = *self.map.get(key)?;
+            self.order.move_to_front(handle);
+            self.order.get(handle).map(|(_, value)| value)
+        }
+
+        fn put(&mut self, key: K, value: V) {
+            if let Some(&handle) = self.map.get(&key) {
+                self.order.get_mut(handle).unwrap().1 = value;
+                self.order.move_to_front(handle);
+                return;
+            }
+
+            if self.order.len() == self.capacity {
+                let (evicted, _) = self.order.pop_back().unwrap();
+                self.map.remove(&evicted);
+            }
+
+            let handle = self.order.push_front((key.clone(), value));
+            self.map.insert(key, handle);
+        }
+    }
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        // "b" foi o menos usado, então é ele que sai
+        cache.put("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"c"), Some(&3));
+
+        cache.put("a", 10);
+        cache.put("d", 4);
+        assert_eq!(cache.get(&"c"), None);
+        assert_eq!(cache.get(&"a"), Some(&10));
+        assert_eq!(cache.get(&"d"), Some(&4));
+
+        // nunca passa da capacidade: as posições despejadas são reaproveitadas
+        for i in 0..100 {
+            cache.put("x", i);
+            cache.put("y", i);
+        }
+        assert_eq!(cache.order.slots(), 2);
+    }
+}
@@ -0,0 +1,453 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{
+    cmp::Reverse,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// Fila de prioridade implementada como um heap binário (max-heap) dentro de um [`Vector`].
+///
+/// A árvore fica "achatada" no vetor: os filhos da posição `i` estão em `2i + 1` e `2i + 2`,
+/// e todo pai é maior ou igual aos filhos. Assim o maior elemento está sempre na posição 0,
+/// e inserir ou remover só mexe num caminho da raiz até uma folha, O(log n).
+///
+/// Pra ter um min-heap, basta guardar os elementos dentro de [`Reverse`]: veja [`MinHeap`].
+pub(crate) struct BinaryHeap<T> {
+    data: Vector<T>,
+}
+
+/// Heap em que o menor elemento sai primeiro. Os elementos entram e saem embrulhados em
+/// [`Reverse`], que inverte a comparação.
+pub(crate) type MinHeap<T> = BinaryHeap<Reverse<T>>;
+
+impl<T: Ord> BinaryHeap<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: Vector::new(),
+        }
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vector::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Maior elemento, sem remover. O(1).
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Acesso mutável ao maior elemento. Se ele for modificado, o heap é arrumado quando o
+    /// [`PeekMut`] sai de escopo.
+    pub(crate) fn peek_mut(&mut self) -> Option<PeekMut<'_, T>> {
+        match self.is_empty() {
+            true => None,
+            false => Some(PeekMut {
+                heap: self,
+                modified: false,
+            }),
+        }
+    }
+
+    /// O(log n).
+    pub(crate) fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    /// Remove e retorna o maior elemento. O(log n).
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        // o último elemento vai pro lugar da raiz e desce até onde couber.
+        let top = self.data.swap_remove(0);
+        if !self.data.is_empty() {
+            self.sift_down(0, self.data.len());
+        }
+
+        Some(top)
+    }
+
+    /// Move todos os elementos de `other` pra esse heap, deixando `other` vazio.
+    pub(crate) fn append(&mut self, other: &mut Self) {
+        // é mais barato encaixar os elementos do menor heap no maior.
+        if self.len() < other.len() {
+            std::mem::swap(self, other);
+        }
+
+        // `other` é o menor dos dois; se ele está vazio não há nada pra encaixar (e o
+        // `ilog2` abaixo entraria em pânico com os dois heaps vazios).
+        if other.is_empty() {
+            return;
+        }
+
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        let total = self.data.len();
+
+        // subir cada elemento novo custa uns `log2(total)` passos; reconstruir o heap inteiro
+        // custa uns `2 * total`. escolhemos o que sair mais barato.
+        let added = total - start;
+        if 2 * total < added * total.ilog2() as usize {
+            self.rebuild();
+        } else {
+            for position in start..total {
+                self.sift_up(position);
+            }
+        }
+    }
+
+    /// Vetor com os elementos em ordem crescente (um heapsort). O(n log n).
+    pub(crate) fn into_sorted_vector(mut self) -> Vector<T> {
+        // a cada passo o maior elemento que sobrou vai pro final da parte ainda não ordenada.
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            self.sift_down(0, end);
+        }
+
+        self.data
+    }
+
+    /// Os elementos na ordem interna do heap, sem custo nenhum.
+    pub(crate) fn into_vector(self) -> Vector<T> {
+        self.data
+    }
+
+    /// Elementos em ordem arbitrária (a ordem interna do heap).
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    // troca o elemento com o pai enquanto ele for maior.
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.data[position] <= self.data[parent] {
+                break;
+            }
+
+            self.data.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    // troca o elemento com o maior filho enquanto algum filho for maior. só olha até `end`.
+    fn sift_down(&mut self, mut position: usize, end: usize) {
+        loop {
+            let mut child = 2 * position + 1;
+            if child >= end {
+                break;
+            }
+
+            if child + 1 < end && self.data[child + 1] > self.data[child] {
+                child += 1;
+            }
+            if self.data[position] >= self.data[child] {
+                break;
+            }
+
+            self.data.swap(position, child);
+            position = child;
+        }
+    }
+
+    // transforma o vetor inteiro num heap de baixo pra cima (Floyd). parece O(n log n), mas
+    // metade dos elementos são folhas (não descem nada), um quarto desce no máximo 1 nível,
+    // e assim por diante: a soma dá O(n).
+    fn rebuild(&mut self) {
+        let length = self.data.len();
+        for position in (0..length / 2).rev() {
+            self.sift_down(position, length);
+        }
+    }
+}
+
+/// Referência mutável pro maior elemento de um [`BinaryHeap`], devolvida pelo `peek_mut`.
+///
+/// Quando sai de escopo, o elemento desce pro lugar certo caso tenha sido modificado.
+pub(crate) struct PeekMut<'a, T: Ord> {
+    heap: &'a mut BinaryHeap<T>,
+    modified: bool,
+}
+
+impl<T: Ord> PeekMut<'_, T> {
+    /// Remove o elemento do heap.
+    pub(crate) fn pop(mut this: Self) -> T {
+        // o `pop` já deixa o heap arrumado, o drop não precisa fazer nada.
+        this.modified = false;
+        this.heap
+            .pop()
+            .expect("PeekMut is only created for non-empty heaps")
+    }
+}
+
+impl<T: Ord> Deref for PeekMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T: Ord> DerefMut for PeekMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T: Ord> Drop for PeekMut<'_, T> {
+    fn drop(&mut self) {
+        // se o `PeekMut` for esquecido com `mem::forget` o heap só fica desordenado, o que
+        // não é inseguro: tudo aqui é código safe.
+        if self.modified {
+            let length = self.heap.data.len();
+            self.heap.sift_down(0, length);
+        }
+    }
+}
+
+impl<T: Ord> From<Vector<T>> for BinaryHeap<T> {
+    /// Reaproveita o vetor e reorganiza ele como heap em O(n).
+    fn from(data: Vector<T>) -> Self {
+        let mut heap = Self { data };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T: Ord, const N: usize> From<[T; N]> for BinaryHeap<T> {
+    fn from(array: [T; N]) -> Self {
+        array.into_iter().collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinaryHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vector<T>>())
+    }
+}
+
+impl<T: Ord> Extend<T> for BinaryHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for BinaryHeap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // xorshift simples, só pra gerar entradas reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed % 1000
+            })
+            .collect()
+    }
+
+    fn drain_all<T: Ord>(heap: &mut BinaryHeap<T>) -> Vec<T> {
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    #[test]
+    fn test_push_pop_peek() {
+        let mut heap = BinaryHeap::new();
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+
+        for x in [3, 1, 4, 1, 5, 9, 2, 6] {
+            heap.push(x);
+        }
+        assert_eq!(heap.len(), 8);
+        assert_eq!(heap.peek(), Some(&9));
+
+        assert_eq!(drain_all(&mut heap), [9, 6, 5, 4, 3, 2, 1, 1]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_matches_sorted_reference() {
+        for (count, seed) in [(0, 1), (1, 2), (2, 3), (100, 4), (5000, 5)] {
+            let values = pseudo_random(count, seed);
+            let mut reference = values.clone();
+            reference.sort_by(|a, b| b.cmp(a));
+
+            // pushes um por um e heapify em O(n) têm que dar no mesmo
+            let mut pushed = BinaryHeap::new();
+            for &value in &values {
+                pushed.push(value);
+            }
+            let mut heapified: BinaryHeap<u64> = values.iter().copied().collect();
+
+            assert_eq!(drain_all(&mut pushed), reference);
+            assert_eq!(drain_all(&mut heapified), reference);
+        }
+    }
+
+    #[test]
+    fn test_interleaved_operations() {
+        let mut heap = BinaryHeap::new();
+        let mut reference: Vec<u64> = Vec::new();
+
+        for (step, value) in pseudo_random(2000, 77).into_iter().enumerate() {
+            if step % 3 == 2 {
+                reference.sort();
+                assert_eq!(heap.pop(), reference.pop());
+            } else {
+                heap.push(value);
+                reference.push(value);
+            }
+            assert_eq!(heap.peek(), reference.iter().max());
+        }
+    }
+
+    #[test]
+    fn test_peek_mut_sifts_on_drop() {
+        let mut heap = BinaryHeap::from([5, 3, 8, 1]);
+
+        // diminuir o topo faz ele descer quando o `PeekMut` sai de escopo
+        if let Some(mut top) = heap.peek_mut() {
+            assert_eq!(*top, 8);
+            *top = 0;
+        }
+        assert_eq!(heap.peek(), Some(&5));
+
+        // só ler não mexe em nada
+        assert_eq!(heap.peek_mut().map(|top| *top), Some(5));
+
+        let top = heap.peek_mut().unwrap();
+        assert_eq!(PeekMut::pop(top), 5);
+        assert_eq!(drain_all(&mut heap), [3, 1, 0]);
+        assert!(heap.peek_mut().is_none());
+    }
+
+    #[test]
+    fn test_into_sorted_vector() {
+        let values = pseudo_random(1000, 9);
+        let heap: BinaryHeap<u64> = values.iter().copied().collect();
+
+        let mut reference = values;
+        reference.sort();
+        assert_eq!(heap.into_sorted_vector(), reference[..]);
+
+        let empty: BinaryHeap<u64> = BinaryHeap::new();
+        assert!(empty.into_sorted_vector().is_empty());
+    }
+
+    #[test]
+    fn test_append() {
+        // uma inserção pequena num heap grande (sobe um por um) e o contrário (reconstrói)
+        for (left, right) in [(1000, 3), (3, 1000), (500, 500), (0, 10), (10, 0), (0, 0)] {
+            let mut a: BinaryHeap<u64> = pseudo_random(left, 11).into_iter().collect();
+            let mut b: BinaryHeap<u64> = pseudo_random(right, 12).into_iter().collect();
+
+            a.append(&mut b);
+            assert!(b.is_empty());
+            assert_eq!(a.len(), left + right);
+
+            let mut reference: Vec<u64> = pseudo_random(left, 11);
+            reference.extend(pseudo_random(right, 12));
+            reference.sort_by(|x, y| y.cmp(x));
+            assert_eq!(drain_all(&mut a), reference);
+        }
+    }
+
+    #[test]
+    fn test_min_heap() {
+        let mut heap: MinHeap<u64> = pseudo_random(500, 13).into_iter().map(Reverse).collect();
+        heap.push(Reverse(0));
+
+        let popped: Vec<u64> = std::iter::from_fn(|| heap.pop().map(|Reverse(x)| x)).collect();
+        let mut reference = pseudo_random(500, 13);
+        reference.push(0);
+        reference.sort();
+        assert_eq!(popped, reference);
+    }
+
+    #[test]
+    fn test_top_k() {
+        // os k maiores com um min-heap de tamanho k: o menor dos k fica no topo e é
+        // substituído sempre que aparece alguém maior.
+        let values = pseudo_random(10_000, 21);
+        let k = 10;
+
+        let mut top: MinHeap<u64> = MinHeap::with_capacity(k);
+        for &value in &values {
+            if top.len() < k {
+                top.push(Reverse(value));
+            } else if let Some(mut smallest) = top.peek_mut()
+                && value > smallest.0
+            {
+                *smallest = Reverse(value);
+            }
+        }
+
+        let mut result: Vec<u64> = top.into_vector().iter().map(|r| r.0).collect();
+        result.sort_by(|a, b| b.cmp(a));
+
+        let mut reference = values;
+        reference.sort_by(|a, b| b.cmp(a));
+        assert_eq!(result, reference[..k]);
+    }
+
+    #[test]
+    fn test_debug_and_clone() {
+        let heap = BinaryHeap::from([1, 2, 3]);
+        assert_eq!(format!("{heap:?}"), "[3, 2, 1]");
+
+        let mut cloned = heap.clone();
+        cloned.push(10);
+        assert_eq!(heap.len(), 3);
+        assert_eq!(cloned.peek(), Some(&10));
+    }
+}
@@ -0,0 +1,395 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+/// Binomial heap (min-heap): uma lista de árvores binomiais, no máximo uma de cada grau.
+///
+/// A árvore de grau `k` tem exatamente `2^k` nodes (são duas de grau `k - 1`, uma pendurada na
+/// outra), então as árvores presentes correspondem aos bits de `n`, e juntar dois heaps é como
+/// somar dois números em binário: árvores de mesmo grau viram uma de grau seguinte, o "vai
+/// um". Tudo custa O(log n).
+pub(crate) struct BinomialHeap<T> {
+    // raízes em ordem crescente de grau, encadeadas por `sibling`.
+    head: Link<T>,
+    // raiz com o menor elemento, pra ter o `peek` em O(1).
+    min: Link<T>,
+    length: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// o `decrease_key` de um binomial heap sobe o elemento trocando com o pai, já que cortar a
+// subárvore estragaria a forma das árvores. se o elemento morasse no node, o handle passaria
+// a apontar pro elemento errado depois da troca. por isso cada elemento fica numa `Entry`
+// separada, que sabe em que node está: a troca só mexe nos ponteiros.
+struct Node<T> {
+    entry: NonNull<Entry<T>>,
+    parent: Link<T>,
+    child: Link<T>,
+    sibling: Link<T>,
+    degree: usize,
+}
+
+struct Entry<T> {
+    element: T,
+    node: NonNull<Node<T>>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+/// Referência pra um elemento que está no heap, devolvida pelo `push` e usada no
+/// `decrease_key`.
+pub(crate) struct Handle<T> {
+    entry: NonNull<Entry<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+unsafe impl<T: Send> Send for BinomialHeap<T> {}
+unsafe impl<T: Sync> Sync for BinomialHeap<T> {}
+
+// elemento guardado no node. só serve pra deixar as comparações legíveis.
+unsafe fn element<'a, T>(node: NonNull<Node<T>>) -> &'a T {
+    unsafe { &(*(*node.as_ptr()).entry.as_ptr()).element }
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: None,
+            min: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// O(log n).
+    pub(crate) fn push(&mut self, element: T) -> Handle<T> {
+        let entry = NonNull::from(Box::leak(Box::new(Entry {
+            element,
+            node: NonNull::dangling(),
+        })));
+        let node = NonNull::from(Box::leak(Box::new(Node {
+            entry,
+            parent: None,
+            child: None,
+            sibling: None,
+            degree: 0,
+        })));
+
+        unsafe {
+            (*entry.as_ptr()).node = node;
+            self.head = Self::union(self.head, Some(node));
+            self.min = Self::find_min(self.head);
+        }
+        self.length += 1;
+
+        Handle { entry }
+    }
+
+    /// Menor elemento, sem remover. O(1).
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.min.map(|min| unsafe { element(min) })
+    }
+
+    /// Remove e retorna o menor elemento. O(log n).
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+
+        unsafe {
+            // tira a raiz mínima da lista de raízes.
+            let mut link = &mut self.head;
+            while *link != Some(min) {
+                link = &mut (*link.expect("min should be a root").as_ptr()).sibling;
+            }
+            *link = (*min.as_ptr()).sibling;
+
+            // os filhos de uma árvore de grau k são árvores de grau k - 1, ..., 0. invertendo
+            // a lista eles viram um heap binomial válido, que é juntado com o resto.
+            let mut children: Link<T> = None;
+            let mut current = (*min.as_ptr()).child;
+            while let Some(child) = current {
+                current = (*child.as_ptr()).sibling;
+                (*child.as_ptr()).sibling = children;
+                (*child.as_ptr()).parent = None;
+                children = Some(child);
+            }
+
+            self.head = Self::union(self.head, children);
+            self.min = Self::find_min(self.head);
+            self.length -= 1;
+
+            let node = Box::from_raw(min.as_ptr());
+            Some(Box::from_raw(node.entry.as_ptr()).element)
+        }
+    }
+
+    /// Move todos os elementos de `other` pra esse heap. O(log n). Os handles de `other`
+    /// continuam valendo, agora nesse heap.
+    pub(crate) fn meld(&mut self, mut other: Self) {
+        unsafe {
+            self.head = Self::union(self.head, other.head.take());
+            self.min = Self::find_min(self.head);
+        }
+        other.min = None;
+        self.length += std::mem::take(&mut other.length);
+    }
+
+    /// Troca o elemento de `handle` por um menor ou igual. O(log n).
+    ///
+    /// # Safety
+    ///
+    /// `handle` precisa ter vindo de um `push` nesse heap (ou num heap que foi juntado a ele
+    /// com `meld`), e o elemento ainda não pode ter sido removido.
+    ///
+    /// # Panics
+    ///
+    /// Se o novo elemento for maior que o atual.
+    pub(crate) unsafe fn decrease_key(&mut self, handle: Handle<T>, element: T) {
+        let entry = handle.entry;
+
+        unsafe {
+            assert!(
+                element <= (*entry.as_ptr()).element,
+                "new element should be <= the current one"
+            );
+            (*entry.as_ptr()).element = element;
+
+            // sobe trocando as entradas com o pai enquanto o pai for maior.
+            let mut node = (*entry.as_ptr()).node;
+            while let Some(parent) = (*node.as_ptr()).parent {
+                if self::element(parent) <= self::element(node) {
+                    break;
+                }
+
+                let parent_entry = (*parent.as_ptr()).entry;
+                (*parent.as_ptr()).entry = entry;
+                (*node.as_ptr()).entry = parent_entry;
+                (*entry.as_ptr()).node = parent;
+                (*parent_entry.as_ptr()).node = node;
+                node = parent;
+            }
+
+            let min = self.min.expect("heap with a handle should not be empty");
+            if (*node.as_ptr()).parent.is_none() && self::element(node) < self::element(min) {
+                self.min = Some(node);
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Graus das árvores na lista de raízes, em ordem. Batem com os bits de `len`.
+    pub(crate) fn degrees(&self) -> Vector<usize> {
+        let mut degrees = Vector::new();
+        let mut current = self.head;
+        while let Some(root) = current {
+            unsafe {
+                degrees.push((*root.as_ptr()).degree);
+                current = (*root.as_ptr()).sibling;
+            }
+        }
+
+        degrees
+    }
+
+    // a soma binária: intercala as duas listas de raízes por grau e depois junta as árvores
+    // de mesmo grau. podem aparecer até três seguidas com o mesmo grau (duas das listas e uma
+    // do "vai um"); nesse caso a primeira fica e as duas seguintes são juntadas.
+    unsafe fn union(a: Link<T>, b: Link<T>) -> Link<T> {
+        unsafe {
+            let mut head = Self::merge_roots(a, b);
+            let mut current = head?;
+
+            let mut prev: Link<T> = None;
+            while let Some(next) = (*current.as_ptr()).sibling {
+                let degree = (*current.as_ptr()).degree;
+                let three_in_a_row = (*next.as_ptr())
+                    .sibling
+                    .is_some_and(|after| (*after.as_ptr()).degree == degree);
+
+                if degree != (*next.as_ptr()).degree || three_in_a_row {
+                    prev = Some(current);
+                    current = next;
+                } else if element(current) <= element(next) {
+                    (*current.as_ptr()).sibling = (*next.as_ptr()).sibling;
+                    Self::link(next, current);
+                } else {
+                    match prev {
+                        Some(prev) => (*prev.as_ptr()).sibling = Some(next),
+                        None => head = Some(next),
+                    }
+                    Self::link(current, next);
+                    current = next;
+                }
+            }
+
+            head
+        }
+    }
+
+    // intercala duas listas de raízes já ordenadas por grau.
+    unsafe fn merge_roots(mut a: Link<T>, mut b: Link<T>) -> Link<T> {
+        unsafe {
+            let mut head = None;
+            let mut tail = &mut head;
+
+            loop {
+                let next = match (a, b) {
+                    (Some(x), Some(y)) if (*x.as_ptr()).degree <= (*y.as_ptr()).degree => {
+                        a = (*x.as_ptr()).sibling;
+                        x
+                    }
+                    (_, Some(y)) => {
+                        b = (*y.as_ptr()).sibling;
+                        y
+                    }
+                    (rest, None) => {
+                        *tail = rest;
+                        return head;
+                    }
+                };
+
+                *tail = Some(next);
+                tail = &mut (*next.as_ptr()).sibling;
+            }
+        }
+    }
+
+    // pendura a árvore `child` na `parent`, que tem o mesmo grau e uma raiz menor ou igual.
+    unsafe fn link(child: NonNull<Node<T>>, parent: NonNull<Node<T>>) {
+        unsafe {
+            (*child.as_ptr()).parent = Some(parent);
+            (*child.as_ptr()).sibling = (*parent.as_ptr()).child;
+            (*parent.as_ptr()).child = Some(child);
+            (*parent.as_ptr()).degree += 1;
+        }
+    }
+
+    unsafe fn find_min(head: Link<T>) -> Link<T> {
+        unsafe {
+            let mut min = head?;
+            let mut current = (*min.as_ptr()).sibling;
+            while let Some(root) = current {
+                if element(root) < element(min) {
+                    min = root;
+                }
+                current = (*root.as_ptr()).sibling;
+            }
+
+            Some(min)
+        }
+    }
+}
+
+// sem `T: Ord` não dá pra usar o `pop`: os nodes (e as entradas) são liberados percorrendo as
+// árvores com uma pilha explícita.
+impl<T> Drop for BinomialHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vector<NonNull<Node<T>>> = self.head.take().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            drop(unsafe { Box::from_raw(node.entry.as_ptr()) });
+            pending.extend(node.child);
+            pending.extend(node.sibling);
+        }
+    }
+}
+
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for BinomialHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BinomialHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BinomialHeap")
+            .field("len", &self.length)
+            .field("min", &self.min.map(|min| unsafe { element(min) }))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap: BinomialHeap<i32> = [5, 3, 8, 1, 9, 2].into_iter().collect();
+        assert_eq!(heap.peek(), Some(&1));
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [1, 2, 3, 5, 8, 9]);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_degrees_follow_binary_representation() {
+        let mut heap = BinomialHeap::new();
+        for n in 1..=100u32 {
+            heap.push(n);
+
+            let bits: Vec<usize> = (0..u32::BITS as usize)
+                .filter(|bit| n >> bit & 1 == 1)
+                .collect();
+            assert_eq!(heap.degrees(), bits[..], "wrong trees for n = {n}");
+        }
+
+        heap.pop();
+        assert_eq!(heap.degrees(), [0, 1, 5, 6]);
+    }
+
+    #[test]
+    fn test_decrease_key_swaps_entries() {
+        let mut heap = BinomialHeap::new();
+        let handles: Vec<_> = (0..64u32).map(|x| heap.push(x + 100)).collect();
+
+        // com 64 elementos sobra uma árvore só, de grau 6: o último handle está no fundo dela
+        assert_eq!(heap.degrees(), [6]);
+        unsafe {
+            heap.decrease_key(handles[63], 5);
+            heap.decrease_key(handles[40], 1);
+            // um handle continua apontando pro próprio elemento mesmo depois de ter sido
+            // empurrado pra baixo pelas subidas dos outros
+            heap.decrease_key(handles[0], 0);
+        }
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).take(4).collect();
+        assert_eq!(popped, [0, 1, 5, 101]);
+    }
+}
@@ -0,0 +1,709 @@
+#![allow(unused)]
+
+use crate::{
+    allocator::{Global, RawAlloc},
+    raw_buffer::{RawBuffer, handle_reserve},
+};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    ptr, slice,
+};
+
+/// Fila de duas pontas (double-ended queue) implementada como um buffer circular.
+///
+/// Os elementos ocupam `length` posições seguidas a partir de `head`, dando a volta no final
+/// do buffer quando necessário. Assim dá pra inserir e remover nas duas pontas em O(1)
+/// sem mover nenhum outro elemento.
+///
+/// ```text
+/// capacidade 8, head = 6, length = 4
+/// [ c | d | _ | _ | _ | _ | a | b ]
+/// ```
+pub(crate) struct Deque<T, A: RawAlloc = Global> {
+    buf: RawBuffer<T, A>,
+    // posição física do primeiro elemento
+    head: usize,
+    length: usize,
+}
+
+impl<T> Deque<T> {
+    pub(crate) fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            buf: handle_reserve(RawBuffer::try_with_capacity_in(capacity, Global)),
+            head: 0,
+            length: 0,
+        }
+    }
+}
+
+impl<T, A: RawAlloc> Deque<T, A> {
+    pub(crate) fn new_in(alloc: A) -> Self {
+        Self {
+            buf: RawBuffer::new_in(alloc),
+            head: 0,
+            length: 0,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub(crate) const fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    // converte o índice lógico (0 = frente da fila) na posição física dentro do buffer.
+    // `head + index` nunca passa de `2 * capacity`, então basta subtrair uma vez em vez de
+    // usar o `%` (que é bem mais caro).
+    fn physical(&self, index: usize) -> usize {
+        let position = self.head.wrapping_add(index);
+
+        match position >= self.capacity() {
+            true => position - self.capacity(),
+            false => position,
+        }
+    }
+
+    fn slot(&self, index: usize) -> *mut T {
+        unsafe { self.buf.ptr().add(self.physical(index)) }
+    }
+
+    pub(crate) fn push_back(&mut self, element: T) {
+        self.grow_if_full();
+
+        unsafe { ptr::write(self.slot(self.length), element) };
+        self.length += 1;
+    }
+
+    pub(crate) fn push_front(&mut self, element: T) {
+        self.grow_if_full();
+
+        // anda o head uma posição pra trás, dando a volta se ele estava no começo do buffer.
+        self.head = match self.head {
+            0 => self.capacity() - 1,
+            head => head - 1,
+        };
+
+        unsafe { ptr::write(self.buf.ptr().add(self.head), element) };
+        self.length += 1;
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        let element = unsafe { ptr::read(self.slot(0)) };
+        self.head = self.physical(1);
+        self.length -= 1;
+
+        Some(element)
+    }
+
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.length -= 1;
+        Some(unsafe { ptr::read(self.slot(self.length)) })
+    }
+
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub(crate) fn back(&self) -> Option<&T> {
+        self.get(self.length.wrapping_sub(1))
+    }
+
+    pub(crate) fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub(crate) fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(self.length.wrapping_sub(1))
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        (index < self.length).then(|| unsafe { &*self.slot(index) })
+    }
+
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        (index < self.length).then(|| unsafe { &mut *self.slot(index) })
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop_back().is_some() {}
+        self.head = 0;
+    }
+
+    /// Os elementos em ordem, divididos em no máximo dois pedaços: do `head` até o fim do
+    /// buffer e, se a fila deu a volta, do começo do buffer em diante.
+    pub(crate) fn as_slices(&self) -> (&[T], &[T]) {
+        let (front, back) = self.slice_ranges();
+
+        unsafe {
+            (
+                slice::from_raw_parts(self.buf.ptr().add(self.head), front),
+                slice::from_raw_parts(self.buf.ptr(), back),
+            )
+        }
+    }
+
+    pub(crate) fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let (front, back) = self.slice_ranges();
+
+        // os dois pedaços nunca se sobrepõem: um termina no fim do buffer
+        // e o outro termina antes do `head`.
+        unsafe {
+            (
+                slice::from_raw_parts_mut(self.buf.ptr().add(self.head), front),
+                slice::from_raw_parts_mut(self.buf.ptr(), back),
+            )
+        }
+    }
+
+    // tamanhos dos dois pedaços do `as_slices`
+    fn slice_ranges(&self) -> (usize, usize) {
+        let until_end = self.capacity() - self.head;
+
+        match self.length <= until_end {
+            true => (self.length, 0),
+            false => (until_end, self.length - until_end),
+        }
+    }
+
+    /// Reorganiza o buffer pra que todos os elementos fiquem num único slice, em ordem.
+    /// O(n), sem alocar nada.
+    pub(crate) fn make_contiguous(&mut self) -> &mut [T] {
+        let (front, back) = self.slice_ranges();
+
+        if back != 0 {
+            // o buffer está assim: [ B | livre | A ], e queremos [ A | B | livre ].
+            // 1. encostamos o A logo depois do B: [ B | A | livre ]
+            // 2. rotacionamos esse trecho pra trocar os dois de lugar.
+            unsafe {
+                let base = self.buf.ptr();
+                ptr::copy(base.add(self.head), base.add(back), front);
+                slice::from_raw_parts_mut(base, self.length).rotate_left(back);
+            }
+
+            self.head = 0;
+        }
+
+        unsafe { slice::from_raw_parts_mut(self.buf.ptr().add(self.head), self.length) }
+    }
+
+    /// Move os `n` primeiros elementos pro final. Move só `min(n, len - n)` elementos.
+    pub(crate) fn rotate_left(&mut self, n: usize) {
+        let length = self.length;
+        assert!(
+            n <= length,
+            "rotation amount (is {n}) should be <= len (is {length})"
+        );
+
+        match n <= length - n {
+            true => (0..n).for_each(|_| self.rotate_once_left()),
+            false => (0..length - n).for_each(|_| self.rotate_once_right()),
+        }
+    }
+
+    /// Move os `n` últimos elementos pro começo.
+    pub(crate) fn rotate_right(&mut self, n: usize) {
+        let length = self.length;
+        assert!(
+            n <= length,
+            "rotation amount (is {n}) should be <= len (is {length})"
+        );
+
+        self.rotate_left(length - n)
+    }
+
+    // como acabamos de tirar um elemento, os `push` nunca precisam crescer o buffer.
+    fn rotate_once_left(&mut self) {
+        if let Some(element) = self.pop_front() {
+            self.push_back(element);
+        }
+    }
+
+    fn rotate_once_right(&mut self) {
+        if let Some(element) = self.pop_back() {
+            self.push_front(element);
+        }
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        let (front, back) = self.as_slices();
+
+        Iter {
+            front: front.iter(),
+            back: back.iter(),
+        }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let (front, back) = self.as_mut_slices();
+
+        IterMut {
+            front: front.iter_mut(),
+            back: back.iter_mut(),
+        }
+    }
+
+    fn grow_if_full(&mut self) {
+        if self.length < self.capacity() {
+            return;
+        }
+
+        let old_capacity = self.capacity();
+        handle_reserve(self.buf.try_reserve(self.length, 1));
+        let new_capacity = self.capacity();
+
+        // o realloc copia o buffer como ele está. se a fila tinha dado a volta,
+        // o pedaço do começo agora está "no meio" e precisa ser desenrolado:
+        //
+        // antes:  [ c | d | a | b ]
+        // depois: [ c | d | a | b | _ | _ | _ | _ ]
+        //
+        // movemos o menor dos dois pedaços pra manter o custo baixo.
+        if self.head + self.length <= old_capacity {
+            return;
+        }
+
+        let front = old_capacity - self.head;
+        let back = self.length - front;
+
+        unsafe {
+            let base = self.buf.ptr();
+
+            if back <= front && back <= new_capacity - old_capacity {
+                // [ c | d | a | b | _ | _ ] -> [ _ | _ | a | b | c | d ]
+                ptr::copy_nonoverlapping(base, base.add(old_capacity), back);
+            } else {
+                // [ d | a | b | c | _ | _ ] -> [ d | _ | _ | _ | a | b | c ]
+                let new_head = new_capacity - front;
+                ptr::copy(base.add(self.head), base.add(new_head), front);
+                self.head = new_head;
+            }
+        }
+    }
+}
+
+impl<T, A: RawAlloc> Index<usize> for Deque<T, A> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.length;
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T, A: RawAlloc> IndexMut<usize> for Deque<T, A> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.length;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T, A: RawAlloc> Drop for Deque<T, A> {
+    fn drop(&mut self) {
+        let (front, back) = self.as_mut_slices();
+        let (front, back) = (front as *mut [T], back as *mut [T]);
+        self.length = 0;
+
+        // se algum elemento do `front` entrar em pânico no drop, o guard ainda dropa o `back`
+        // durante o unwind, senão ele vazaria inteiro.
+        struct Dropper<T>(*mut [T]);
+
+        impl<T> Drop for Dropper<T> {
+            fn drop(&mut self) {
+                unsafe { ptr::drop_in_place(self.0) }
+            }
+        }
+
+        // o buffer em si é liberado pelo `RawBuffer`.
+        let _back = Dropper(back);
+        unsafe { ptr::drop_in_place(front) }
+    }
+}
+
+impl<T> Default for Deque<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: fmt::Debug, A: RawAlloc> fmt::Debug for Deque<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> FromIterator<T> for Deque<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Deque::new();
+        deque.extend(iter);
+
+        deque
+    }
+}
+
+impl<T, A: RawAlloc> Extend<T> for Deque<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<T, A: RawAlloc> IntoIterator for Deque<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> IntoIter<T, A> {
+        IntoIter { deque: self }
+    }
+}
+
+impl<'a, T, A: RawAlloc> IntoIterator for &'a Deque<T, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T, A: RawAlloc> IntoIterator for &'a mut Deque<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Iterador criado por [`Deque::iter`]: percorre os dois pedaços do buffer em sequência.
+pub(crate) struct Iter<'a, T> {
+    front: slice::Iter<'a, T>,
+    back: slice::Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.front.len() + self.back.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Iterador criado por [`Deque::iter_mut`].
+pub(crate) struct IterMut<'a, T> {
+    front: slice::IterMut<'a, T>,
+    back: slice::IterMut<'a, T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.front.next().or_else(|| self.back.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.front.len() + self.back.len();
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.back.next_back().or_else(|| self.front.next_back())
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// Iterador que consome a [`Deque`], tirando os elementos pela frente (ou por trás).
+pub(crate) struct IntoIter<T, A: RawAlloc = Global> {
+    deque: Deque<T, A>,
+}
+
+impl<T, A: RawAlloc> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.deque.len(), Some(self.deque.len()))
+    }
+}
+
+impl<T, A: RawAlloc> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        self.deque.pop_back()
+    }
+}
+
+impl<T, A: RawAlloc> ExactSizeIterator for IntoIter<T, A> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut deque = Deque::new();
+        deque.push_back(2);
+        deque.push_back(3);
+        deque.push_front(1);
+        deque.push_front(0);
+
+        assert_eq!(deque.len(), 4);
+        assert_eq!(deque.front(), Some(&0));
+        assert_eq!(deque.back(), Some(&3));
+
+        // fifo
+        assert_eq!(deque.pop_front(), Some(0));
+        assert_eq!(deque.pop_back(), Some(3));
+        assert_eq!(deque.pop_front(), Some(1));
+        assert_eq!(deque.pop_front(), Some(2));
+        assert_eq!(deque.pop_front(), None);
+        assert_eq!(deque.pop_back(), None);
+        assert_eq!(deque.back(), None);
+    }
+
+    #[test]
+    fn test_wrap_around_and_index() {
+        let mut deque = Deque::with_capacity(4);
+        deque.push_back(1);
+        deque.push_back(2);
+        deque.pop_front();
+        deque.pop_front();
+
+        // head está no meio do buffer, então esses pushes dão a volta
+        for i in 0..4 {
+            deque.push_back(i);
+        }
+        assert_eq!(deque.capacity(), 4);
+
+        let (front, back) = deque.as_slices();
+        assert_eq!(front, &[0, 1]);
+        assert_eq!(back, &[2, 3]);
+
+        deque[2] = 20;
+        assert_eq!(deque[2], 20);
+        assert_eq!(deque.get(4), None);
+    }
+
+    #[test]
+    fn test_growth_unwraps_ring() {
+        // pedaço de trás menor: ele é copiado pro final do buffer novo
+        let mut deque = Deque::with_capacity(4);
+        for i in 0..4 {
+            deque.push_back(i);
+        }
+        deque.pop_front();
+        deque.pop_front();
+        deque.push_back(4);
+        deque.push_back(5);
+        // [ 4 | 5 | 2 | 3 ] cheio, e o próximo push cresce
+        deque.push_back(6);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [2, 3, 4, 5, 6]);
+
+        // pedaço da frente menor: ele é movido pro final do buffer novo
+        let mut deque = Deque::with_capacity(4);
+        for i in 1..4 {
+            deque.push_back(i);
+        }
+        deque.push_front(0);
+        deque.push_front(-1);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), [-1, 0, 1, 2, 3]);
+
+        // muitos pushes misturados nas duas pontas
+        let mut deque = Deque::new();
+        let mut reference = std::collections::VecDeque::new();
+        for i in 0..1000 {
+            if i % 3 == 0 {
+                deque.push_front(i);
+                reference.push_front(i);
+            } else {
+                deque.push_back(i);
+                reference.push_back(i);
+            }
+
+            if i % 7 == 0 {
+                assert_eq!(deque.pop_front(), reference.pop_front());
+            }
+        }
+        assert!(deque.iter().eq(reference.iter()));
+    }
+
+    #[test]
+    fn test_make_contiguous() {
+        let mut deque = Deque::with_capacity(8);
+        for i in 3..8 {
+            deque.push_back(i);
+        }
+        for i in (0..3).rev() {
+            deque.push_front(i);
+        }
+        assert_ne!(deque.as_slices().1.len(), 0);
+
+        assert_eq!(deque.make_contiguous(), &[0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(deque.as_slices(), (&[0, 1, 2, 3, 4, 5, 6, 7][..], &[][..]));
+
+        // continua funcionando normalmente depois
+        deque.push_front(-1);
+        assert_eq!(deque[0], -1);
+        assert_eq!(deque[8], 7);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut deque: Deque<i32> = (0..6).collect();
+
+        deque.rotate_left(2);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [2, 3, 4, 5, 0, 1]
+        );
+
+        deque.rotate_right(2);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4, 5]
+        );
+
+        deque.rotate_left(5);
+        assert_eq!(
+            deque.iter().copied().collect::<Vec<_>>(),
+            [5, 0, 1, 2, 3, 4]
+        );
+
+        deque.rotate_left(0);
+        deque.rotate_right(6);
+        assert_eq!(deque.front(), Some(&5));
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut deque: Deque<String> = (0..3).map(|i| format!("String {}", i)).collect();
+        deque.push_front(String::from("front"));
+
+        for s in &mut deque {
+            s.push('!');
+        }
+
+        let mut iter = deque.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next_back().map(String::as_str), Some("String 2!"));
+        assert_eq!(iter.next().map(String::as_str), Some("front!"));
+
+        let owned: Vec<String> = deque.into_iter().rev().collect();
+        assert_eq!(owned, ["String 2!", "String 1!", "String 0!", "front!"]);
+    }
+
+    #[test]
+    fn test_drop() {
+        let tracker = Rc::new(());
+
+        {
+            let mut deque = Deque::with_capacity(4);
+            for _ in 0..3 {
+                deque.push_back(Rc::clone(&tracker));
+            }
+            deque.pop_front();
+            // dá a volta no buffer
+            deque.push_back(Rc::clone(&tracker));
+            deque.push_back(Rc::clone(&tracker));
+            assert_eq!(Rc::strong_count(&tracker), 5);
+        }
+
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        let mut into_iter = (0..4)
+            .map(|_| Rc::clone(&tracker))
+            .collect::<Deque<_>>()
+            .into_iter();
+        into_iter.next();
+        drop(into_iter);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_drop_panic_still_drops_back() {
+        // entra em pânico no drop se `panics`, e sempre solta o `Rc`
+        struct Bomb(Rc<()>, bool);
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                if self.1 {
+                    panic!("boom");
+                }
+            }
+        }
+
+        let tracker = Rc::new(());
+        let mut deque = Deque::with_capacity(4);
+        deque.push_back(Bomb(Rc::clone(&tracker), false));
+        deque.push_back(Bomb(Rc::clone(&tracker), false));
+        deque.pop_front();
+        deque.pop_front();
+        // o primeiro pedaço fica no fim do buffer e o segundo, no começo
+        deque.push_back(Bomb(Rc::clone(&tracker), true));
+        deque.push_back(Bomb(Rc::clone(&tracker), false));
+        deque.push_back(Bomb(Rc::clone(&tracker), false));
+        deque.push_back(Bomb(Rc::clone(&tracker), false));
+        assert_ne!(deque.as_slices().1.len(), 0);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(deque)));
+        assert!(result.is_err());
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut deque = Deque::new();
+        deque.push_front(());
+        deque.push_back(());
+        deque.push_front(());
+
+        assert_eq!(deque.len(), 3);
+        assert_eq!(deque.iter().count(), 3);
+        assert_eq!(deque.pop_back(), Some(()));
+        assert_eq!(deque.pop_front(), Some(()));
+        assert_eq!(deque.len(), 1);
+    }
+}
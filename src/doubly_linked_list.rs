@@ -0,0 +1,869 @@
+#![allow(unused)]
+
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+/// Lista duplamente encadeada: cada node aponta pro anterior e pro próximo.
+///
+/// Diferente da [`LinkedList`](crate::linked_list::LinkedList), aqui temos acesso O(1) às duas
+/// pontas e podemos remover ou inserir no meio sem percorrer nada, desde que já estejamos
+/// "parados" no lugar certo com um [`CursorMut`].
+pub(crate) struct DoublyLinkedList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    length: usize,
+    // a lista é dona dos nodes, mesmo guardando só ponteiros crus. o `PhantomData` avisa isso
+    // pro compilador, que então trata a lista como se tivesse um `Box<Node<T>>` (importante
+    // pro drop check e pra variância).
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+struct Node<T> {
+    element: T,
+    prev: Link<T>,
+    next: Link<T>,
+}
+
+// com dois ponteiros pro mesmo node (o `next` do anterior e o `prev` do próximo), não dá pra
+// usar `Box`, que exige um único dono. usamos `NonNull` e cuidamos da memória na mão.
+type Link<T> = Option<NonNull<Node<T>>>;
+
+// mesma coisa do `RawBuffer`: os ponteiros crus tiram o send/sync automático, mas a lista
+// se comporta como um `Box<Node<T>>`, então é seguro devolver.
+unsafe impl<T: Send> Send for DoublyLinkedList<T> {}
+unsafe impl<T: Sync> Sync for DoublyLinkedList<T> {}
+
+impl<T> Node<T> {
+    // todo node nasce num `Box` e só volta a ser um `Box` (e ser liberado) quando sai da lista.
+    fn new(element: T) -> NonNull<Self> {
+        let node = Box::new(Self {
+            element,
+            prev: None,
+            next: None,
+        });
+
+        NonNull::from(Box::leak(node))
+    }
+}
+
+impl<T> DoublyLinkedList<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: None,
+            tail: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub(crate) fn push_front(&mut self, element: T) {
+        let node = Node::new(element);
+        unsafe { self.link_between(None, self.head, node, node, 1) }
+    }
+
+    pub(crate) fn push_back(&mut self, element: T) {
+        let node = Node::new(element);
+        unsafe { self.link_between(self.tail, None, node, node, 1) }
+    }
+
+    pub(crate) fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub(crate) fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub(crate) fn front(&self) -> Option<&T> {
+        self.head.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    pub(crate) fn front_mut(&mut self) -> Option<&mut T> {
+        self.head
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    pub(crate) fn back(&self) -> Option<&T> {
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).element })
+    }
+
+    pub(crate) fn back_mut(&mut self) -> Option<&mut T> {
+        self.tail
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            front: self.head,
+            back: self.tail,
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: self.head,
+            back: self.tail,
+            remaining: self.length,
+            marker: PhantomData,
+        }
+    }
+
+    /// Cursor parado no primeiro elemento (ou no "fantasma", se a lista estiver vazia).
+    pub(crate) fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Cursor parado no último elemento (ou no "fantasma", se a lista estiver vazia).
+    pub(crate) fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.length.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    // pendura a corrente `first..=last` (com `count` nodes) entre `prev` e `next`, que precisam
+    // ser vizinhos nessa lista. `None` em `prev` quer dizer "antes da head" e em `next`,
+    // "depois da tail". é a base de todas as inserções: push, insert e splice.
+    //
+    // sempre lemos e escrevemos os campos pelo ponteiro cru, sem criar `&mut Node`, assim
+    // nunca invalidamos referências pros elementos que alguém ainda possa estar segurando.
+    unsafe fn link_between(
+        &mut self,
+        prev: Link<T>,
+        next: Link<T>,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        count: usize,
+    ) {
+        unsafe {
+            (*first.as_ptr()).prev = prev;
+            (*last.as_ptr()).next = next;
+
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(first),
+                None => self.head = Some(first),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(last),
+                None => self.tail = Some(last),
+            }
+        }
+
+        self.length += count;
+    }
+
+    // tira o node da lista, costurando os vizinhos, e devolve o elemento dele.
+    // `node` precisa pertencer a essa lista.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // voltar pra `Box` devolve a posse do node pra gente; ele é liberado no final.
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+
+        unsafe {
+            match node.prev {
+                Some(prev) => (*prev.as_ptr()).next = node.next,
+                None => self.head = node.next,
+            }
+            match node.next {
+                Some(next) => (*next.as_ptr()).prev = node.prev,
+                None => self.tail = node.prev,
+            }
+        }
+
+        self.length -= 1;
+        node.element
+    }
+
+    // corta a lista logo depois de `node` (ou antes da head, se for `None`), deixando `kept`
+    // elementos aqui e devolvendo o resto. O(1) porque quem chama já sabe quantos ficam.
+    unsafe fn split_after_node(&mut self, node: Link<T>, kept: usize) -> Self {
+        let first = match node {
+            Some(node) => unsafe { (*node.as_ptr()).next.take() },
+            None => self.head.take(),
+        };
+
+        let Some(first) = first else {
+            return Self::new();
+        };
+
+        unsafe { (*first.as_ptr()).prev = None };
+        let rest = Self {
+            head: Some(first),
+            tail: self.tail,
+            length: self.length - kept,
+            marker: PhantomData,
+        };
+
+        self.tail = node;
+        self.length = kept;
+        rest
+    }
+
+    // igual ao `split_after_node`, mas devolve tudo que vem antes de `node`.
+    unsafe fn split_before_node(&mut self, node: Link<T>, kept: usize) -> Self {
+        let last = match node {
+            Some(node) => unsafe { (*node.as_ptr()).prev.take() },
+            None => self.tail.take(),
+        };
+
+        let Some(last) = last else {
+            return Self::new();
+        };
+
+        unsafe { (*last.as_ptr()).next = None };
+        let rest = Self {
+            head: self.head,
+            tail: Some(last),
+            length: self.length - kept,
+            marker: PhantomData,
+        };
+
+        self.head = node;
+        self.length = kept;
+        rest
+    }
+
+    // pendura todos os nodes de `other` entre `prev` e `next`, sem alocar nem liberar nada.
+    // `other` fica vazia, então o drop dela não encosta nos nodes que agora são nossos.
+    unsafe fn splice_between(&mut self, prev: Link<T>, next: Link<T>, mut other: Self) {
+        if let (Some(first), Some(last)) = (other.head.take(), other.tail.take()) {
+            let count = std::mem::take(&mut other.length);
+            unsafe { self.link_between(prev, next, first, last, count) }
+        }
+    }
+}
+
+/// Cursor que anda pela lista e pode editá-la no ponto onde está parado.
+///
+/// Entre a tail e a head existe um elemento "fantasma", que não guarda nada: andar pra frente
+/// a partir da tail (ou pra trás a partir da head) para nele, e andar mais uma vez dá a volta.
+pub(crate) struct CursorMut<'a, T> {
+    list: &'a mut DoublyLinkedList<T>,
+    current: Link<T>,
+    // posição do `current`. no fantasma, é igual ao tamanho da lista.
+    index: usize,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Posição do cursor, ou `None` se ele estiver no fantasma.
+    pub(crate) fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub(crate) fn move_next(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    pub(crate) fn move_prev(&mut self) {
+        match self.current {
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = match self.current {
+                    Some(_) => self.index - 1,
+                    None => self.list.length,
+                };
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.length.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Elemento onde o cursor está parado.
+    pub(crate) fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    pub(crate) fn peek_next(&mut self) -> Option<&mut T> {
+        self.next_node()
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    pub(crate) fn peek_prev(&mut self) -> Option<&mut T> {
+        self.prev_node()
+            .map(|node| unsafe { &mut (*node.as_ptr()).element })
+    }
+
+    /// Insere logo depois do cursor. No fantasma, vira a nova head.
+    pub(crate) fn insert_after(&mut self, element: T) {
+        let node = Node::new(element);
+        let next = self.next_node();
+        unsafe { self.list.link_between(self.current, next, node, node, 1) };
+
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Insere logo antes do cursor. No fantasma, vira a nova tail.
+    pub(crate) fn insert_before(&mut self, element: T) {
+        let node = Node::new(element);
+        let prev = self.prev_node();
+        unsafe { self.list.link_between(prev, self.current, node, node, 1) };
+
+        self.index += 1;
+    }
+
+    /// Remove o elemento atual e anda pro próximo.
+    pub(crate) fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        self.current = unsafe { (*node.as_ptr()).next };
+
+        // o próximo elemento herda a posição do que saiu, então o índice não muda.
+        Some(unsafe { self.list.unlink(node) })
+    }
+
+    /// Devolve uma lista nova com tudo que vem depois do cursor.
+    pub(crate) fn split_after(&mut self) -> DoublyLinkedList<T> {
+        let kept = match self.current {
+            Some(_) => self.index + 1,
+            None => 0,
+        };
+
+        if self.current.is_none() {
+            self.index = 0;
+        }
+
+        unsafe { self.list.split_after_node(self.current, kept) }
+    }
+
+    /// Devolve uma lista nova com tudo que vem antes do cursor.
+    pub(crate) fn split_before(&mut self) -> DoublyLinkedList<T> {
+        let kept = self.list.length - self.index;
+        self.index = 0;
+
+        unsafe { self.list.split_before_node(self.current, kept) }
+    }
+
+    /// Move todos os elementos de `other` pra logo depois do cursor.
+    pub(crate) fn splice_after(&mut self, other: DoublyLinkedList<T>) {
+        let count = other.len();
+        let next = self.next_node();
+        unsafe { self.list.splice_between(self.current, next, other) };
+
+        if self.current.is_none() {
+            self.index += count;
+        }
+    }
+
+    /// Move todos os elementos de `other` pra logo antes do cursor.
+    pub(crate) fn splice_before(&mut self, other: DoublyLinkedList<T>) {
+        let count = other.len();
+        let prev = self.prev_node();
+        unsafe { self.list.splice_between(prev, self.current, other) };
+
+        self.index += count;
+    }
+
+    fn next_node(&self) -> Link<T> {
+        match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        }
+    }
+
+    fn prev_node(&self) -> Link<T> {
+        match self.current {
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        }
+    }
+}
+
+impl<T> Drop for DoublyLinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for DoublyLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for DoublyLinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DoublyLinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DoublyLinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DoublyLinkedList<T> {}
+
+impl<T> FromIterator<T> for DoublyLinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for DoublyLinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<T> IntoIterator for DoublyLinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DoublyLinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DoublyLinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+/// Iterador que empresta os elementos da lista, podendo andar pelas duas pontas.
+pub(crate) struct Iter<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    // as duas pontas se encontram no meio; contar o que falta é o jeito mais simples de
+    // saber quando parar sem devolver o mesmo node duas vezes.
+    remaining: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.front.map(|node| unsafe {
+            self.remaining -= 1;
+            self.front = (*node.as_ptr()).next;
+            &(*node.as_ptr()).element
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.back.map(|node| unsafe {
+            self.remaining -= 1;
+            self.back = (*node.as_ptr()).prev;
+            &(*node.as_ptr()).element
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+/// Iterador que empresta os elementos da lista de forma mutável, pelas duas pontas.
+pub(crate) struct IterMut<'a, T> {
+    front: Link<T>,
+    back: Link<T>,
+    remaining: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.front.map(|node| unsafe {
+            self.remaining -= 1;
+            self.front = (*node.as_ptr()).next;
+            &mut (*node.as_ptr()).element
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for IterMut<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.back.map(|node| unsafe {
+            self.remaining -= 1;
+            self.back = (*node.as_ptr()).prev;
+            &mut (*node.as_ptr()).element
+        })
+    }
+}
+
+impl<T> ExactSizeIterator for IterMut<'_, T> {}
+
+/// Iterador que consome a lista.
+pub(crate) struct IntoIter<T>(DoublyLinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+// os testes usam listas pequenas de propósito: todos rodam rápido no miri
+// (`cargo +nightly miri test doubly_linked_list`), que é quem valida os ponteiros crus.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{collections::VecDeque, rc::Rc};
+
+    fn to_vec<T: Clone>(list: &DoublyLinkedList<T>) -> Vec<T> {
+        list.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = DoublyLinkedList::new();
+        list.push_back(2);
+        list.push_back(3);
+        list.push_front(1);
+        list.push_front(0);
+
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.front(), Some(&0));
+        assert_eq!(list.back(), Some(&3));
+
+        *list.front_mut().unwrap() = 10;
+        *list.back_mut().unwrap() = 30;
+
+        assert_eq!(list.pop_front(), Some(10));
+        assert_eq!(list.pop_back(), Some(30));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.pop_front(), None);
+        assert!(list.is_empty());
+        assert_eq!(list.front(), None);
+    }
+
+    #[test]
+    fn test_iterators_both_directions() {
+        let mut list: DoublyLinkedList<i32> = (1..=5).collect();
+
+        assert_eq!(
+            list.iter().rev().copied().collect::<Vec<_>>(),
+            [5, 4, 3, 2, 1]
+        );
+
+        // as duas pontas se encontram no meio sem repetir nenhum elemento
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&5));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        for element in &mut list {
+            *element *= 10;
+        }
+        // segurar várias referências mutáveis ao mesmo tempo é válido: cada uma aponta pra
+        // um node diferente.
+        let mut iter = list.iter_mut();
+        let first = iter.next().unwrap();
+        let last = iter.next_back().unwrap();
+        std::mem::swap(first, last);
+
+        assert_eq!(to_vec(&list), [50, 20, 30, 40, 10]);
+        assert_eq!(
+            list.into_iter().rev().collect::<Vec<_>>(),
+            [10, 40, 30, 20, 50]
+        );
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let mut list: DoublyLinkedList<i32> = (0..3).collect();
+        let mut cursor = list.cursor_front_mut();
+
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(2));
+
+        // passando da tail caímos no fantasma...
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 0));
+        assert_eq!(cursor.peek_prev(), Some(&mut 2));
+
+        // ...e mais um passo dá a volta pra head
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_prev();
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 2));
+
+        let mut empty: DoublyLinkedList<i32> = DoublyLinkedList::new();
+        let mut cursor = empty.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn test_cursor_insert_and_remove() {
+        let mut list: DoublyLinkedList<i32> = [1, 3].into_iter().collect();
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.insert_after(2);
+        cursor.insert_before(0);
+        assert_eq!(cursor.index(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 1));
+
+        // no fantasma, insert_after vira push_front e insert_before vira push_back
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        cursor.insert_after(-1);
+        cursor.insert_before(4);
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(5));
+        assert_eq!(to_vec(&list), [-1, 0, 1, 2, 3, 4]);
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.remove_current(), Some(1));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        assert_eq!(cursor.index(), Some(2));
+
+        // removendo a tail o cursor cai no fantasma
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(4));
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.remove_current(), None);
+
+        assert_eq!(to_vec(&list), [-1, 0, 2, 3]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.back(), Some(&3));
+    }
+
+    #[test]
+    fn test_cursor_split() {
+        let mut list: DoublyLinkedList<i32> = (0..6).collect();
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+        let after = cursor.split_after();
+        assert_eq!(cursor.index(), Some(2));
+        let before = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+
+        assert_eq!(to_vec(&before), [0, 1]);
+        assert_eq!(to_vec(&list), [2]);
+        assert_eq!(to_vec(&after), [3, 4, 5]);
+        assert_eq!((before.len(), list.len(), after.len()), (2, 1, 3));
+        assert_eq!(after.back(), Some(&5));
+        assert_eq!(before.back(), Some(&1));
+
+        // no fantasma, split_after leva tudo e split_before também
+        let mut list: DoublyLinkedList<i32> = (0..3).collect();
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        assert_eq!(to_vec(&cursor.split_after()), [0, 1, 2]);
+        assert!(list.is_empty());
+
+        let mut list: DoublyLinkedList<i32> = (0..3).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_prev();
+        assert_eq!(to_vec(&cursor.split_before()), [0, 1, 2]);
+        assert!(list.is_empty());
+
+        // cortar na ponta devolve uma lista vazia
+        let mut list: DoublyLinkedList<i32> = (0..3).collect();
+        assert!(list.cursor_back_mut().split_after().is_empty());
+        assert!(list.cursor_front_mut().split_before().is_empty());
+        assert_eq!(list.len(), 3);
+    }
+
+    #[test]
+    fn test_cursor_splice() {
+        let mut list: DoublyLinkedList<i32> = [0, 5].into_iter().collect();
+
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after((3..5).collect());
+        cursor.splice_after((1..3).collect());
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_next();
+        cursor.splice_before([10, 11].into_iter().collect());
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(cursor.current(), Some(&mut 1));
+
+        // juntar uma lista vazia não muda nada
+        cursor.splice_before(DoublyLinkedList::new());
+        assert_eq!(cursor.index(), Some(3));
+
+        // no fantasma, splice_after vai pra frente e splice_before pro final
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        cursor.splice_after([-2, -1].into_iter().collect());
+        cursor.splice_before([6, 7].into_iter().collect());
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(11));
+
+        assert_eq!(to_vec(&list), [-2, -1, 0, 10, 11, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(list.len(), 12);
+        assert_eq!(list.iter().rev().count(), 12);
+    }
+
+    #[test]
+    fn test_matches_vec_deque() {
+        let mut list = DoublyLinkedList::new();
+        let mut reference = VecDeque::new();
+
+        for i in 0..200 {
+            match i % 7 {
+                0 | 3 => {
+                    list.push_back(i);
+                    reference.push_back(i);
+                }
+                1 | 4 => {
+                    list.push_front(i);
+                    reference.push_front(i);
+                }
+                2 => assert_eq!(list.pop_front(), reference.pop_front()),
+                5 => assert_eq!(list.pop_back(), reference.pop_back()),
+                _ => {
+                    let mut cursor = list.cursor_front_mut();
+                    cursor.move_next();
+                    let removed = cursor.remove_current();
+                    assert_eq!(removed, reference.remove(1));
+                }
+            }
+
+            assert_eq!(list.len(), reference.len());
+        }
+
+        assert!(list.iter().eq(reference.iter()));
+        assert!(list.iter().rev().eq(reference.iter().rev()));
+    }
+
+    #[test]
+    fn test_drop_and_clone() {
+        let tracker = Rc::new(());
+
+        {
+            let mut list: DoublyLinkedList<Rc<()>> = (0..5).map(|_| Rc::clone(&tracker)).collect();
+            let copy = list.clone();
+            assert_eq!(Rc::strong_count(&tracker), 11);
+
+            // pedaços cortados e costurados continuam sendo dropados uma vez só
+            let mut cursor = list.cursor_front_mut();
+            cursor.move_next();
+            let tail = cursor.split_after();
+            drop(cursor.remove_current());
+            assert_eq!(Rc::strong_count(&tracker), 10);
+
+            list.cursor_front_mut().splice_before(tail);
+            assert_eq!(list.len(), 4);
+            drop(copy);
+            assert_eq!(Rc::strong_count(&tracker), 5);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        let list: DoublyLinkedList<i32> = (0..3).collect();
+        assert_eq!(format!("{list:?}"), "[0, 1, 2]");
+        assert_eq!(list.clone(), list);
+    }
+}
@@ -0,0 +1,440 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+/// Fibonacci heap (min-heap): uma coleção de árvores em ordem de heap, com as raízes numa
+/// lista circular.
+///
+/// É o heap mais preguiçoso de todos: `push` e `meld` só colocam árvores na lista de raízes e
+/// `decrease_key` só corta o node do pai, tudo em O(1) amortizado. A arrumação acontece no
+/// `pop`, que junta as raízes de mesmo grau até sobrar no máximo uma de cada, O(log n)
+/// amortizado. É o que deixa Dijkstra e Prim em O(E + V log V).
+pub(crate) struct FibonacciHeap<T> {
+    min: Link<T>,
+    length: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// os irmãos (e as raízes) ficam em listas circulares duplamente encadeadas por `left` e
+// `right`, então dá pra tirar um node ou emendar duas listas em O(1). um node sozinho aponta
+// pra si mesmo. `marked` diz se o node já perdeu um filho desde que virou filho de alguém.
+struct Node<T> {
+    element: T,
+    parent: Link<T>,
+    child: Link<T>,
+    left: NonNull<Node<T>>,
+    right: NonNull<Node<T>>,
+    degree: usize,
+    marked: bool,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+/// Referência pra um elemento que está no heap, devolvida pelo `push` e usada no
+/// `decrease_key`.
+pub(crate) struct Handle<T> {
+    node: NonNull<Node<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+unsafe impl<T: Send> Send for FibonacciHeap<T> {}
+unsafe impl<T: Sync> Sync for FibonacciHeap<T> {}
+
+// emenda a lista circular de `b` logo depois de `a`.
+unsafe fn splice<T>(a: NonNull<Node<T>>, b: NonNull<Node<T>>) {
+    unsafe {
+        let a_right = (*a.as_ptr()).right;
+        let b_left = (*b.as_ptr()).left;
+
+        (*a.as_ptr()).right = b;
+        (*b.as_ptr()).left = a;
+        (*b_left.as_ptr()).right = a_right;
+        (*a_right.as_ptr()).left = b_left;
+    }
+}
+
+// tira o node da lista circular em que ele está, deixando ele sozinho.
+unsafe fn unlink<T>(node: NonNull<Node<T>>) {
+    unsafe {
+        let left = (*node.as_ptr()).left;
+        let right = (*node.as_ptr()).right;
+
+        (*left.as_ptr()).right = right;
+        (*right.as_ptr()).left = left;
+        (*node.as_ptr()).left = node;
+        (*node.as_ptr()).right = node;
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            min: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// O(1).
+    pub(crate) fn push(&mut self, element: T) -> Handle<T> {
+        let node = NonNull::from(Box::leak(Box::new(Node {
+            element,
+            parent: None,
+            child: None,
+            left: NonNull::dangling(),
+            right: NonNull::dangling(),
+            degree: 0,
+            marked: false,
+        })));
+
+        unsafe {
+            (*node.as_ptr()).left = node;
+            (*node.as_ptr()).right = node;
+            self.add_root(node);
+        }
+        self.length += 1;
+
+        Handle { node }
+    }
+
+    /// Menor elemento, sem remover. O(1).
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.min.map(|min| unsafe { &(*min.as_ptr()).element })
+    }
+
+    /// Remove e retorna o menor elemento. O(log n) amortizado.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+
+        unsafe {
+            // os filhos do mínimo viram raízes.
+            if let Some(child) = (*min.as_ptr()).child.take() {
+                let mut current = child;
+                loop {
+                    (*current.as_ptr()).parent = None;
+                    (*current.as_ptr()).marked = false;
+                    current = (*current.as_ptr()).right;
+                    if current == child {
+                        break;
+                    }
+                }
+                splice(min, child);
+            }
+
+            let next = (*min.as_ptr()).right;
+            unlink(min);
+            self.min = match next == min {
+                true => None,
+                false => Some(self.consolidate(next)),
+            };
+            self.length -= 1;
+
+            Some(Box::from_raw(min.as_ptr()).element)
+        }
+    }
+
+    /// Move todos os elementos de `other` pra esse heap. O(1). Os handles de `other` continuam
+    /// valendo, agora nesse heap.
+    pub(crate) fn meld(&mut self, mut other: Self) {
+        if let Some(other_min) = other.min.take() {
+            unsafe { self.add_root(other_min) };
+            self.length += std::mem::take(&mut other.length);
+        }
+    }
+
+    /// Troca o elemento de `handle` por um menor ou igual. O(1) amortizado.
+    ///
+    /// # Safety
+    ///
+    /// `handle` precisa ter vindo de um `push` nesse heap (ou num heap que foi juntado a ele
+    /// com `meld`), e o elemento ainda não pode ter sido removido.
+    ///
+    /// # Panics
+    ///
+    /// Se o novo elemento for maior que o atual.
+    pub(crate) unsafe fn decrease_key(&mut self, handle: Handle<T>, element: T) {
+        let node = handle.node;
+
+        unsafe {
+            assert!(
+                element <= (*node.as_ptr()).element,
+                "new element should be <= the current one"
+            );
+            (*node.as_ptr()).element = element;
+
+            if let Some(parent) = (*node.as_ptr()).parent
+                && (*node.as_ptr()).element < (*parent.as_ptr()).element
+            {
+                self.cut(node, parent);
+
+                // corte em cascata: um node que perde o segundo filho também é cortado. é o
+                // que garante que uma árvore de grau k tenha pelo menos F(k + 2) nodes (daí o
+                // nome), e portanto que o grau fique em O(log n).
+                let mut current = parent;
+                while let Some(grandparent) = (*current.as_ptr()).parent {
+                    if !(*current.as_ptr()).marked {
+                        (*current.as_ptr()).marked = true;
+                        break;
+                    }
+
+                    self.cut(current, grandparent);
+                    current = grandparent;
+                }
+            }
+
+            let min = self.min.expect("heap with a handle should not be empty");
+            if (*node.as_ptr()).element < (*min.as_ptr()).element {
+                self.min = Some(node);
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    /// Quantidade de árvores na lista de raízes.
+    pub(crate) fn root_count(&self) -> usize {
+        let Some(min) = self.min else {
+            return 0;
+        };
+
+        let mut count = 1;
+        let mut current = unsafe { (*min.as_ptr()).right };
+        while current != min {
+            count += 1;
+            current = unsafe { (*current.as_ptr()).right };
+        }
+
+        count
+    }
+
+    // coloca uma lista circular de raízes junto das outras, atualizando o mínimo.
+    unsafe fn add_root(&mut self, node: NonNull<Node<T>>) {
+        unsafe {
+            match self.min {
+                Some(min) => {
+                    splice(min, node);
+                    if (*node.as_ptr()).element < (*min.as_ptr()).element {
+                        self.min = Some(node);
+                    }
+                }
+                None => self.min = Some(node),
+            }
+        }
+    }
+
+    // tira `node` dos filhos de `parent` e coloca ele como raiz.
+    unsafe fn cut(&mut self, node: NonNull<Node<T>>, parent: NonNull<Node<T>>) {
+        unsafe {
+            if (*parent.as_ptr()).child == Some(node) {
+                let right = (*node.as_ptr()).right;
+                (*parent.as_ptr()).child = (right != node).then_some(right);
+            }
+            unlink(node);
+            (*parent.as_ptr()).degree -= 1;
+
+            (*node.as_ptr()).parent = None;
+            (*node.as_ptr()).marked = false;
+            self.add_root(node);
+        }
+    }
+
+    // junta raízes de mesmo grau até sobrar no máximo uma de cada. `by_degree[d]` guarda a
+    // raiz de grau `d` vista até agora. retorna a nova raiz mínima.
+    unsafe fn consolidate(&mut self, start: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        unsafe {
+            // a lista de raízes muda enquanto juntamos, então primeiro anotamos quem está nela.
+            let mut roots = Vector::new();
+            let mut current = start;
+            loop {
+                roots.push(current);
+                current = (*current.as_ptr()).right;
+                if current == start {
+                    break;
+                }
+            }
+
+            let mut by_degree: Vector<Link<T>> = Vector::new();
+            for mut root in roots {
+                let mut degree = (*root.as_ptr()).degree;
+                loop {
+                    while by_degree.len() <= degree {
+                        by_degree.push(None);
+                    }
+                    let Some(mut other) = by_degree[degree].take() else {
+                        break;
+                    };
+
+                    if (*other.as_ptr()).element < (*root.as_ptr()).element {
+                        std::mem::swap(&mut root, &mut other);
+                    }
+                    Self::link(other, root);
+                    degree += 1;
+                }
+                by_degree[degree] = Some(root);
+            }
+
+            let mut roots = by_degree.iter().flatten().copied();
+            let first = roots.next().expect("heap should not be empty");
+            roots.fold(first, |min, root| {
+                match (*root.as_ptr()).element < (*min.as_ptr()).element {
+                    true => root,
+                    false => min,
+                }
+            })
+        }
+    }
+
+    // pendura a raiz `child` como filha da raiz `parent`.
+    unsafe fn link(child: NonNull<Node<T>>, parent: NonNull<Node<T>>) {
+        unsafe {
+            unlink(child);
+            (*child.as_ptr()).parent = Some(parent);
+            (*child.as_ptr()).marked = false;
+
+            match (*parent.as_ptr()).child {
+                Some(first) => splice(first, child),
+                None => (*parent.as_ptr()).child = Some(child),
+            }
+            (*parent.as_ptr()).degree += 1;
+        }
+    }
+}
+
+// sem `T: Ord` não dá pra usar o `pop`. cada lista circular pendente é percorrida uma vez, e
+// as listas de filhos vão pra pilha à medida que os nodes são liberados.
+impl<T> Drop for FibonacciHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vector<NonNull<Node<T>>> = self.min.take().into_iter().collect();
+
+        while let Some(start) = pending.pop() {
+            let mut current = start;
+            loop {
+                let node = unsafe { Box::from_raw(current.as_ptr()) };
+                pending.extend(node.child);
+
+                // só comparamos o endereço, sem ler nada do node já liberado.
+                if node.right == start {
+                    break;
+                }
+                current = node.right;
+            }
+        }
+    }
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for FibonacciHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for FibonacciHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FibonacciHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FibonacciHeap")
+            .field("len", &self.length)
+            .field(
+                "min",
+                &self.min.map(|min| unsafe { &(*min.as_ptr()).element }),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap: FibonacciHeap<i32> = [5, 3, 8, 1, 9, 2].into_iter().collect();
+        assert_eq!(heap.peek(), Some(&1));
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [1, 2, 3, 5, 8, 9]);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_consolidation() {
+        // antes do primeiro pop as raízes são só uma lista; depois dele sobra no máximo uma
+        // árvore por grau, uma pra cada bit de n
+        let mut heap: FibonacciHeap<u32> = (0..101).collect();
+        assert_eq!(heap.root_count(), 101);
+
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.root_count(), 100u32.count_ones() as usize);
+    }
+
+    #[test]
+    fn test_cascading_cut() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<_> = (0..33u32).map(|x| heap.push(x + 100)).collect();
+
+        // sobra uma árvore de grau 5 com os 32 que restaram
+        assert_eq!(heap.pop(), Some(100));
+        assert_eq!(heap.root_count(), 1);
+
+        // um node que não é raiz e tem pelo menos dois filhos
+        let parent = handles
+            .iter()
+            .skip(1)
+            .map(|handle| handle.node)
+            .find(|&node| unsafe {
+                (*node.as_ptr()).parent.is_some() && (*node.as_ptr()).degree >= 2
+            })
+            .unwrap();
+
+        unsafe {
+            let first = (*parent.as_ptr()).child.unwrap();
+            let second = (*first.as_ptr()).right;
+
+            // o primeiro corte só marca o pai; o segundo corta ele também
+            heap.decrease_key(Handle { node: first }, 0);
+            assert!((*parent.as_ptr()).marked);
+            assert!((*parent.as_ptr()).parent.is_some());
+
+            heap.decrease_key(Handle { node: second }, 1);
+            assert!((*parent.as_ptr()).parent.is_none());
+            assert!(!(*parent.as_ptr()).marked);
+        }
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped.len(), 32);
+        assert_eq!(popped[..2], [0, 1]);
+        assert!(popped.is_sorted());
+    }
+}
@@ -0,0 +1,1120 @@
+#![allow(unused)]
+
+use crate::vector::{self, Vector};
+use std::{
+    borrow::Borrow,
+    fmt,
+    hash::{BuildHasher, Hash, RandomState},
+    ops::Index,
+};
+
+/// Tabela hash com endereçamento aberto: os pares ficam direto num [`Vector`] de slots, e uma
+/// colisão é resolvida procurando o próximo slot livre (sondagem linear).
+///
+/// O problema da sondagem linear é que alguns elementos acabam muito longe do slot ideal
+/// deles. O Robin Hood equilibra isso: na inserção, quem está longe de casa "rouba" o slot de
+/// quem está perto e o empurra pra frente. A distância máxima cai bastante, e uma busca pode
+/// parar assim que encontrar alguém mais perto de casa do que ela estaria.
+///
+/// A quantidade de slots é sempre uma potência de 2, e a tabela dobra de tamanho quando passa
+/// de 7/8 de ocupação.
+pub(crate) struct HashMap<K, V, S = RandomState> {
+    slots: Vector<Option<Bucket<K, V>>>,
+    length: usize,
+    hasher: S,
+}
+
+// o hash fica guardado pra não precisar recalcular na hora de redimensionar nem pra saber a
+// distância do slot ideal, e também pra comparar hashes antes de comparar chaves.
+struct Bucket<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+const MIN_SLOTS: usize = 8;
+
+// quantos elementos cabem em `slots` slots sem passar de 7/8 de ocupação.
+const fn max_load(slots: usize) -> usize {
+    slots / 8 * 7
+}
+
+/// Distâncias entre o slot em que cada elemento está e o slot ideal dele (o do hash).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProbeStats {
+    /// Maior distância, ou seja, o máximo de slots extras que uma busca bem-sucedida olha.
+    pub(crate) max: usize,
+    pub(crate) mean: f64,
+    /// `histogram[d]` é quantos elementos estão a `d` slots do ideal.
+    pub(crate) histogram: Vector<usize>,
+}
+
+impl<K: Hash + Eq, V> HashMap<K, V, RandomState> {
+    pub(crate) fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> HashMap<K, V, S> {
+    /// Tabela vazia, sem alocar nada, usando `hasher` pra calcular os hashes.
+    pub(crate) fn with_hasher(hasher: S) -> Self {
+        Self {
+            slots: Vector::new(),
+            length: 0,
+            hasher,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Quantos elementos cabem antes de a tabela precisar crescer.
+    pub(crate) fn capacity(&self) -> usize {
+        max_load(self.slots.len())
+    }
+
+    /// Fração dos slots que está ocupada.
+    pub(crate) fn load_factor(&self) -> f64 {
+        match self.slots.len() {
+            0 => 0.0,
+            slots => self.length as f64 / slots as f64,
+        }
+    }
+
+    pub(crate) fn hasher(&self) -> &S {
+        &self.hasher
+    }
+
+    /// Remove tudo, mas mantém os slots alocados.
+    pub(crate) fn clear(&mut self) {
+        // o `Drain` mantém o `length` certo a cada par removido, mesmo se o drop de algum
+        // entrar em pânico.
+        self.drain().for_each(drop);
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            slots: self.slots.iter(),
+            remaining: self.length,
+        }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            slots: self.slots.iter_mut(),
+            remaining: self.length,
+        }
+    }
+
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.iter_mut().map(|(_, value)| value)
+    }
+
+    /// Tira todos os pares da tabela, que continua com os slots alocados. Os pares que o
+    /// iterador não chegar a devolver são descartados quando ele sai de escopo. Se ele for
+    /// esquecido com `mem::forget`, esses pares só continuam na tabela.
+    pub(crate) fn drain(&mut self) -> Drain<'_, K, V, S> {
+        // a ocupação nunca passa de 7/8, então sempre existe um slot vazio (se houver slots).
+        let start = self.slots.iter().position(Option::is_none).unwrap_or(0);
+        Drain {
+            unvisited: self.slots.len(),
+            index: start,
+            map: self,
+        }
+    }
+
+    /// Estatísticas das distâncias de sondagem, pra ver o quanto os elementos estão longe do
+    /// slot ideal. Com o Robin Hood a média fica perto de 1 mesmo com a tabela bem cheia.
+    pub(crate) fn probe_stats(&self) -> ProbeStats {
+        let mut histogram = Vector::new();
+        let mut total = 0;
+
+        for (index, slot) in self.slots.iter().enumerate() {
+            let Some(bucket) = slot else { continue };
+
+            let distance = self.distance(bucket.hash, index);
+            while histogram.len() <= distance {
+                histogram.push(0);
+            }
+            histogram[distance] += 1;
+            total += distance;
+        }
+
+        ProbeStats {
+            max: histogram.len().saturating_sub(1),
+            mean: match self.length {
+                0 => 0.0,
+                length => total as f64 / length as f64,
+            },
+            histogram,
+        }
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    // slot ideal de um hash: os bits de baixo, já que a quantidade de slots é potência de 2.
+    fn ideal(&self, hash: u64) -> usize {
+        hash as usize & self.mask()
+    }
+
+    // quantos slots depois do ideal está um elemento com esse hash guardado em `index`.
+    fn distance(&self, hash: u64, index: usize) -> usize {
+        index.wrapping_sub(self.ideal(hash)) & self.mask()
+    }
+
+    // coloca um par que com certeza não está na tabela, que já precisa ter espaço pra ele.
+    // retorna o slot em que o par foi parar (os que ele desalojou vão pra frente).
+    fn insert_new(&mut self, hash: u64, key: K, value: V) -> usize {
+        let mut bucket = Bucket { hash, key, value };
+        let mut index = self.ideal(hash);
+        let mut distance = 0;
+        let mut landed = None;
+        let mask = self.mask();
+
+        loop {
+            match &mut self.slots[index] {
+                slot @ None => {
+                    *slot = Some(bucket);
+                    self.length += 1;
+                    return landed.unwrap_or(index);
+                }
+                Some(existing) => {
+                    // quem já está ali está mais perto de casa do que nós: ele cede o slot e
+                    // passa a ser o par que procura lugar.
+                    let existing_distance =
+                        index.wrapping_sub(existing.hash as usize & mask) & mask;
+                    if existing_distance < distance {
+                        std::mem::swap(existing, &mut bucket);
+                        landed.get_or_insert(index);
+                        distance = existing_distance;
+                    }
+                }
+            }
+
+            index = (index + 1) & mask;
+            distance += 1;
+        }
+    }
+
+    // tira o par do slot e puxa uma posição pra trás os seguintes, até achar um slot vazio ou
+    // um elemento que já está no ideal. assim não precisamos de marcadores de "removido", e
+    // as distâncias continuam certas pras buscas pararem cedo.
+    fn remove_at(&mut self, mut index: usize) -> Bucket<K, V> {
+        let removed = self.slots[index].take().expect("slot should be occupied");
+        self.length -= 1;
+
+        loop {
+            let next = (index + 1) & self.mask();
+            match &self.slots[next] {
+                Some(bucket) if self.distance(bucket.hash, next) > 0 => {
+                    self.slots[index] = self.slots[next].take();
+                    index = next;
+                }
+                _ => break,
+            }
+        }
+
+        removed
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> HashMap<K, V, S> {
+    pub(crate) fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        let mut map = Self::with_hasher(hasher);
+        map.reserve(capacity);
+        map
+    }
+
+    /// Garante espaço pra mais `additional` elementos sem redimensionar.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        let needed = self
+            .length
+            .checked_add(additional)
+            .expect("capacity overflow");
+        if needed <= self.capacity() {
+            return;
+        }
+
+        let mut slots = self.slots.len().max(MIN_SLOTS);
+        while max_load(slots) < needed {
+            slots = slots.checked_mul(2).expect("capacity overflow");
+        }
+        self.resize(slots);
+    }
+
+    /// Diminui a quantidade de slots o máximo possível.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let mut slots = MIN_SLOTS;
+        while max_load(slots) < self.length {
+            slots *= 2;
+        }
+
+        match self.length {
+            0 => self.slots = Vector::new(),
+            _ if slots < self.slots.len() => self.resize(slots),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_ref().map(|bucket| &bucket.value)
+    }
+
+    pub(crate) fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index].as_mut().map(|bucket| &mut bucket.value)
+    }
+
+    pub(crate) fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        self.slots[index]
+            .as_ref()
+            .map(|bucket| (&bucket.key, &bucket.value))
+    }
+
+    pub(crate) fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Associa `value` a `key`, retornando o valor antigo se a chave já existia (a chave em
+    /// si não é trocada).
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.remove_entry(key).map(|(_, value)| value)
+    }
+
+    pub(crate) fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let index = self.find(key)?;
+        let bucket = self.remove_at(index);
+        Some((bucket.key, bucket.value))
+    }
+
+    /// Lugar da chave na tabela, ocupado ou não, pra ler, inserir ou alterar sem calcular o
+    /// hash e procurar duas vezes.
+    pub(crate) fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        let hash = self.hasher.hash_one(&key);
+        if let Some(index) = self.find_hashed(hash, &key) {
+            return Entry::Occupied(OccupiedEntry { map: self, index });
+        }
+
+        self.reserve(1);
+        Entry::Vacant(VacantEntry {
+            map: self,
+            hash,
+            key,
+        })
+    }
+
+    /// Mantém só os pares para os quais `keep` retorna `true`.
+    pub(crate) fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut keep: F) {
+        if self.length == 0 {
+            return;
+        }
+
+        // a remoção puxa os vizinhos da frente pra trás, então depois de remover olhamos o
+        // mesmo slot de novo. começando logo depois de um slot vazio (sempre existe um, a
+        // tabela nunca lota), ninguém dá a volta no vetor e volta pra um slot já visitado.
+        let mask = self.mask();
+        let empty = self
+            .slots
+            .iter()
+            .position(Option::is_none)
+            .expect("table is never full");
+
+        let mut visited = 0;
+        while visited < self.slots.len() {
+            let index = (empty + 1 + visited) & mask;
+            let remove = match &mut self.slots[index] {
+                Some(bucket) => !keep(&bucket.key, &mut bucket.value),
+                None => false,
+            };
+
+            match remove {
+                true => {
+                    self.remove_at(index);
+                }
+                false => visited += 1,
+            }
+        }
+    }
+
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        if self.length == 0 {
+            return None;
+        }
+
+        self.find_hashed(self.hasher.hash_one(key), key)
+    }
+
+    // anda a partir do slot ideal. dá pra desistir ao achar um slot vazio ou um elemento mais
+    // perto de casa do que a chave estaria ali: pelo Robin Hood, ela teria tomado esse slot.
+    fn find_hashed<Q>(&self, hash: u64, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Eq + ?Sized,
+    {
+        if self.slots.is_empty() {
+            return None;
+        }
+
+        let mut index = self.ideal(hash);
+        let mut distance = 0;
+
+        loop {
+            let bucket = self.slots[index].as_ref()?;
+            if self.distance(bucket.hash, index) < distance {
+                return None;
+            }
+            if bucket.hash == hash && bucket.key.borrow() == key {
+                return Some(index);
+            }
+
+            index = (index + 1) & self.mask();
+            distance += 1;
+        }
+    }
+
+    // reinsere tudo numa tabela com `slots` slots. os hashes já estão guardados.
+    fn resize(&mut self, slots: usize) {
+        let mut new_slots = Vector::with_capacity(slots);
+        for _ in 0..slots {
+            new_slots.push(None);
+        }
+
+        let old = std::mem::replace(&mut self.slots, new_slots);
+        self.length = 0;
+        for bucket in old.into_iter().flatten() {
+            self.insert_new(bucket.hash, bucket.key, bucket.value);
+        }
+    }
+}
+
+/// Resultado do `entry`: a chave já está na tabela ou ainda não.
+pub(crate) enum Entry<'a, K, V, S> {
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+pub(crate) struct OccupiedEntry<'a, K, V, S> {
+    map: &'a mut HashMap<K, V, S>,
+    index: usize,
+}
+
+pub(crate) struct VacantEntry<'a, K, V, S> {
+    map: &'a mut HashMap<K, V, S>,
+    hash: u64,
+    key: K,
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S> {
+    pub(crate) fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub(crate) fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub(crate) fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub(crate) fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Chama `f` no valor se a chave já existir.
+    pub(crate) fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+
+        self
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S> {
+    fn bucket(&self) -> &Bucket<K, V> {
+        self.map.slots[self.index]
+            .as_ref()
+            .expect("occupied entry points to a bucket")
+    }
+
+    fn bucket_mut(&mut self) -> &mut Bucket<K, V> {
+        self.map.slots[self.index]
+            .as_mut()
+            .expect("occupied entry points to a bucket")
+    }
+
+    pub(crate) fn key(&self) -> &K {
+        &self.bucket().key
+    }
+
+    pub(crate) fn get(&self) -> &V {
+        &self.bucket().value
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut V {
+        &mut self.bucket_mut().value
+    }
+
+    /// Referência pro valor que vive tanto quanto o empréstimo da tabela.
+    pub(crate) fn into_mut(self) -> &'a mut V {
+        &mut self.map.slots[self.index]
+            .as_mut()
+            .expect("occupied entry points to a bucket")
+            .value
+    }
+
+    /// Troca o valor, retornando o antigo.
+    pub(crate) fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    pub(crate) fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    pub(crate) fn remove_entry(self) -> (K, V) {
+        let bucket = self.map.remove_at(self.index);
+        (bucket.key, bucket.value)
+    }
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S> {
+    pub(crate) fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub(crate) fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Coloca o par na tabela. O `entry` já reservou espaço, então aqui nunca redimensiona.
+    pub(crate) fn insert(self, value: V) -> &'a mut V {
+        let index = self.map.insert_new(self.hash, self.key, value);
+        &mut self.map.slots[index]
+            .as_mut()
+            .expect("bucket was just inserted")
+            .value
+    }
+}
+
+pub(crate) struct Iter<'a, K, V> {
+    slots: std::slice::Iter<'a, Option<Bucket<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((&bucket.key, &bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+pub(crate) struct IterMut<'a, K, V> {
+    slots: std::slice::IterMut<'a, Option<Bucket<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((&bucket.key, &mut bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+pub(crate) struct IntoIter<K, V> {
+    slots: vector::IntoIter<Option<Bucket<K, V>>>,
+    remaining: usize,
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bucket = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        Some((bucket.key, bucket.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+// anda pra trás a partir de um slot vazio, removendo cada par com o `remove_at` normal. como
+// o slot seguinte já está vazio, quem sai é sempre o último do agrupamento e ninguém precisa
+// ser puxado: cada remoção é O(1) e a tabela continua válida a cada passo, então um `Drain`
+// esquecido no meio deixa pra trás um mapa normal com os pares que faltavam.
+pub(crate) struct Drain<'a, K, V, S> {
+    map: &'a mut HashMap<K, V, S>,
+    index: usize,
+    unvisited: usize,
+}
+
+impl<K, V, S> Iterator for Drain<'_, K, V, S> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.unvisited > 0 {
+            self.unvisited -= 1;
+            self.index = self.index.checked_sub(1).unwrap_or(self.map.mask());
+
+            if self.map.slots[self.index].is_some() {
+                let bucket = self.map.remove_at(self.index);
+                return Some((bucket.key, bucket.value));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.map.length, Some(self.map.length))
+    }
+}
+
+impl<K, V, S> ExactSizeIterator for Drain<'_, K, V, S> {}
+
+impl<K, V, S> Drop for Drain<'_, K, V, S> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+impl<K, V, S> IntoIterator for HashMap<K, V, S> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            remaining: self.length,
+            slots: self.slots.into_iter(),
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashMap<K, V, S> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashMap<K, V, S> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, Q, V, S> Index<&Q> for HashMap<K, V, S>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in HashMap")
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher + Default> FromIterator<(K, V)> for HashMap<K, V, S> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> Extend<(K, V)> for HashMap<K, V, S> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K: Hash + Eq, V, const N: usize> From<[(K, V); N]> for HashMap<K, V, RandomState> {
+    fn from(pairs: [(K, V); N]) -> Self {
+        pairs.into_iter().collect()
+    }
+}
+
+impl<K, V, S: Default> Default for HashMap<K, V, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K: Clone, V: Clone, S: Clone> Clone for HashMap<K, V, S> {
+    fn clone(&self) -> Self {
+        let mut slots = Vector::with_capacity(self.slots.len());
+        for slot in self.slots.iter() {
+            slots.push(slot.as_ref().map(|bucket| Bucket {
+                hash: bucket.hash,
+                key: bucket.key.clone(),
+                value: bucket.value.clone(),
+            }));
+        }
+
+        Self {
+            slots,
+            length: self.length,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+// duas tabelas são iguais se têm os mesmos pares, não importa a ordem dos slots.
+impl<K: Hash + Eq, V: PartialEq, S: BuildHasher> PartialEq for HashMap<K, V, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<K: Hash + Eq, V: Eq, S: BuildHasher> Eq for HashMap<K, V, S> {}
+
+impl<K: fmt::Debug, V: fmt::Debug, S> fmt::Debug for HashMap<K, V, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{BuildHasherDefault, DefaultHasher, Hasher};
+
+    // xorshift simples, só pra gerar entradas reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            })
+            .collect()
+    }
+
+    // hasher péssimo de propósito: só olha o primeiro byte e ainda gera só 4 hashes
+    // diferentes, então qualquer chave colide com muitas outras e os agrupamentos ficam enormes
+    #[derive(Default)]
+    struct TerribleHasher(u64);
+
+    impl Hasher for TerribleHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+
+        fn write(&mut self, bytes: &[u8]) {
+            if let Some(&last) = bytes.first() {
+                self.0 = last as u64 % 4;
+            }
+        }
+    }
+
+    type Colliding<K, V> = HashMap<K, V, BuildHasherDefault<TerribleHasher>>;
+
+    // toda busca só pode parar cedo se as distâncias ao longo de cada agrupamento nunca
+    // aumentam em mais de 1 de um slot pro próximo
+    fn assert_robin_hood<K, V, S>(map: &HashMap<K, V, S>) {
+        let slots = map.slots.len();
+        let mut count = 0;
+        for index in 0..slots {
+            let Some(bucket) = &map.slots[index] else {
+                continue;
+            };
+            count += 1;
+
+            let distance = map.distance(bucket.hash, index);
+            if distance > 0 {
+                let prev = &map.slots[(index + slots - 1) % slots];
+                let prev = prev.as_ref().expect("element away from home after a hole");
+                assert!(map.distance(prev.hash, (index + slots - 1) % slots) + 1 >= distance);
+            }
+        }
+        assert_eq!(count, map.len());
+        assert!(map.len() <= map.capacity());
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = HashMap::new();
+        assert_eq!(map.get("a"), None);
+        assert_eq!(map.remove("a"), None);
+
+        assert_eq!(map.insert("a".to_string(), 1), None);
+        assert_eq!(map.insert("b".to_string(), 2), None);
+        assert_eq!(map.insert("a".to_string(), 3), Some(1));
+        assert_eq!(map.len(), 2);
+
+        assert_eq!(map.get("a"), Some(&3));
+        assert_eq!(map["b"], 2);
+        assert!(map.contains_key("b"));
+        *map.get_mut("b").unwrap() += 10;
+        assert_eq!(map.get_key_value("b"), Some((&"b".to_string(), &12)));
+
+        assert_eq!(map.remove("a"), Some(3));
+        assert_eq!(map.remove_entry("b"), Some(("b".to_string(), 12)));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "key not found in HashMap")]
+    fn test_index_missing_key() {
+        let map: HashMap<u32, u32> = HashMap::new();
+        let _ = map[&1];
+    }
+
+    #[test]
+    fn test_random_operations_against_std() {
+        let mut map = HashMap::new();
+        let mut reference = std::collections::HashMap::new();
+
+        for random in pseudo_random(20_000, 17) {
+            let key = random % 2000;
+            match random % 5 {
+                0..=2 => assert_eq!(map.insert(key, random), reference.insert(key, random)),
+                3 => assert_eq!(map.remove(&key), reference.remove(&key)),
+                _ => assert_eq!(map.get(&key), reference.get(&key)),
+            }
+            assert_eq!(map.len(), reference.len());
+        }
+
+        assert_robin_hood(&map);
+        for (key, value) in &reference {
+            assert_eq!(map.get(key), Some(value));
+        }
+    }
+
+    #[test]
+    fn test_heavy_collisions() {
+        // com só 4 hashes diferentes tudo vira um agrupamento gigante: exercita as trocas do
+        // Robin Hood e o deslocamento pra trás na remoção
+        let mut map: Colliding<u32, u32> = Colliding::default();
+        for key in 0..300 {
+            map.insert(key, key * 2);
+        }
+        assert_robin_hood(&map);
+
+        for key in (0..300).step_by(3) {
+            assert_eq!(map.remove(&key), Some(key * 2));
+            assert_robin_hood(&map);
+        }
+        for key in 0..300 {
+            let expected = (key % 3 != 0).then_some(key * 2);
+            assert_eq!(map.get(&key).copied(), expected);
+        }
+    }
+
+    #[test]
+    fn test_entry() {
+        let text = "o rato roeu a roupa do rei de roma e o rei riu";
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for word in text.split(' ') {
+            *counts.entry(word).or_default() += 1;
+        }
+        assert_eq!(counts["rei"], 2);
+        assert_eq!(counts["o"], 2);
+        assert_eq!(counts["roma"], 1);
+
+        let value = counts.entry("rato").and_modify(|c| *c += 10).or_insert(0);
+        assert_eq!(*value, 11);
+        let value = counts.entry("gato").and_modify(|c| *c += 10).or_insert(7);
+        assert_eq!(*value, 7);
+
+        match counts.entry("riu") {
+            Entry::Occupied(entry) => {
+                assert_eq!(entry.key(), &"riu");
+                assert_eq!(entry.remove(), 1);
+            }
+            Entry::Vacant(_) => unreachable!(),
+        }
+        match counts.entry("riu") {
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), "riu"),
+            Entry::Occupied(_) => unreachable!(),
+        }
+        assert!(!counts.contains_key("riu"));
+    }
+
+    #[test]
+    fn test_resize_and_capacity() {
+        let mut map = HashMap::new();
+        assert_eq!(map.capacity(), 0);
+
+        for key in 0..1000u32 {
+            map.insert(key, ());
+            assert!(map.load_factor() <= 0.875);
+        }
+        assert_eq!(map.slots.len(), 2048);
+
+        map.retain(|&key, _| key < 10);
+        map.shrink_to_fit();
+        assert_eq!(map.slots.len(), 16);
+        assert_robin_hood(&map);
+        assert_eq!(map.len(), 10);
+
+        let map: HashMap<u32, u32> = HashMap::with_capacity(100);
+        assert!(map.capacity() >= 100);
+        assert_eq!(map.slots.len(), 128);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut map: Colliding<u32, u32> = (0..500).map(|k| (k, k)).collect();
+        let mut calls = 0;
+        map.retain(|&key, value| {
+            calls += 1;
+            *value += 1;
+            key % 2 == 0
+        });
+
+        // cada par visto uma vez só, mesmo com os deslocamentos
+        assert_eq!(calls, 500);
+        assert_eq!(map.len(), 250);
+        assert_robin_hood(&map);
+        assert!(
+            map.iter()
+                .all(|(&key, &value)| key % 2 == 0 && value == key + 1)
+        );
+    }
+
+    #[test]
+    fn test_iterators_and_drain() {
+        let mut map: HashMap<u32, u32> = (0..100).map(|k| (k, k * k)).collect();
+        assert_eq!(map.iter().len(), 100);
+        assert_eq!(map.keys().copied().sum::<u32>(), 4950);
+
+        for value in map.values_mut() {
+            *value += 1;
+        }
+        assert_eq!(map[&9], 82);
+
+        let drained: Vec<_> = map.drain().take(10).collect();
+        assert_eq!(drained.len(), 10);
+        assert!(map.is_empty());
+        assert_eq!(map.iter().count(), 0);
+        assert!(map.capacity() >= 100);
+
+        map.insert(1, 1);
+        let pairs: Vec<_> = map.into_iter().collect();
+        assert_eq!(pairs, [(1, 1)]);
+    }
+
+    #[test]
+    fn test_drain_forget_leaks_gracefully() {
+        let mut map: Colliding<u32, String> = (0..100).map(|k| (k, k.to_string())).collect();
+
+        let mut drain = map.drain();
+        let (key, value) = drain.next().expect("map is not empty");
+        assert_eq!(value, key.to_string());
+        let (other, _) = drain.next().expect("map has two pairs");
+        std::mem::forget(drain);
+
+        // sem o drop do `Drain` os pares que faltavam ficam, e o mapa continua consistente
+        assert_eq!(map.len(), 98);
+        assert_robin_hood(&map);
+        assert_eq!(map.iter().count(), 98);
+        for k in 0..100 {
+            let expected = (k != key && k != other).then(|| k.to_string());
+            assert_eq!(map.get(&k), expected.as_ref());
+        }
+
+        map.insert(key, String::from("again"));
+        assert_eq!(map.len(), 99);
+        assert_eq!(map.drain().count(), 99);
+        assert!(map.is_empty());
+        assert_robin_hood(&map);
+    }
+
+    #[test]
+    fn test_probe_stats() {
+        let mut map: HashMap<u32, u32, BuildHasherDefault<DefaultHasher>> = HashMap::default();
+        for key in 0..1700u32 {
+            map.insert(key, key);
+        }
+
+        // 1700 elementos em 2048 slots, 83% cheio
+        let stats = map.probe_stats();
+        assert_eq!(stats.histogram.iter().sum::<usize>(), map.len());
+        assert_eq!(stats.max, stats.histogram.len() - 1);
+        assert!(
+            stats.mean < 3.0,
+            "mean probe length too high: {}",
+            stats.mean
+        );
+
+        let empty: HashMap<u32, u32> = HashMap::new();
+        assert_eq!(empty.probe_stats().max, 0);
+        assert_eq!(empty.probe_stats().mean, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_reserve_overflow() {
+        let mut map: HashMap<u32, u32> = HashMap::new();
+        map.reserve(usize::MAX / 2);
+    }
+
+    #[test]
+    fn test_clear_panic_keeps_map_consistent() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        // explode no drop se o `bool` for `true`
+        struct Bomb(bool);
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                assert!(!self.0, "boom");
+            }
+        }
+
+        let mut map: HashMap<u32, Bomb> = (0..100).map(|k| (k, Bomb(k == 50))).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| map.clear()));
+        assert!(result.is_err());
+
+        // o `Drain` do `clear` continua removendo o resto durante o unwind
+        assert_eq!(map.len(), 0);
+        assert_eq!(map.iter().len(), map.iter().count());
+        assert_robin_hood(&map);
+        map.insert(1, Bomb(false));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_drops_everything() {
+        use std::rc::Rc;
+
+        let value = Rc::new(());
+        {
+            let mut map = HashMap::new();
+            for key in 0..200 {
+                map.insert(key, Rc::clone(&value));
+            }
+            map.remove(&0);
+            map.insert(1, Rc::clone(&value));
+            map.drain().take(5).for_each(drop);
+            assert_eq!(Rc::strong_count(&value), 1);
+
+            for key in 0..50 {
+                map.insert(key, Rc::clone(&value));
+            }
+            assert_eq!(Rc::strong_count(&value), 51);
+        }
+
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn test_clone_eq_debug() {
+        let map = HashMap::from([(1, "um"), (2, "dois")]);
+        let mut cloned = map.clone();
+        assert_eq!(map, cloned);
+
+        cloned.insert(3, "três");
+        assert_ne!(map, cloned);
+
+        let single = HashMap::from([(1, "um")]);
+        assert_eq!(format!("{single:?}"), r#"{1: "um"}"#);
+    }
+}
@@ -0,0 +1,452 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{borrow::Borrow, collections::HashMap, fmt, hash::Hash};
+
+/// Min-heap indexado: cada elemento tem uma chave `K` e uma prioridade `P`, e dá pra mudar a
+/// prioridade de uma chave que já está no heap.
+///
+/// Além do heap binário num [`Vector`], um mapa guarda em que posição do vetor cada chave
+/// está. Assim achar uma chave é O(1), e depois ela só precisa subir ou descer: todas as
+/// operações ficam em O(log n). É o que algoritmos como Dijkstra e Prim precisam.
+pub(crate) struct IndexedHeap<K, P> {
+    data: Vector<(K, P)>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: Vector::new(),
+            positions: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vector::with_capacity(capacity),
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(crate) fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+
+    /// Prioridade atual de `key`.
+    pub(crate) fn priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.positions.get(key)?;
+        Some(&self.data[position].1)
+    }
+
+    /// Insere `key` com a prioridade dada. Se a chave já estava no heap, a prioridade dela é
+    /// trocada (pra cima ou pra baixo) e a antiga é retornada.
+    pub(crate) fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if let Some(&position) = self.positions.get(&key) {
+            return Some(self.replace_at(position, priority));
+        }
+
+        let position = self.data.len();
+        self.positions.insert(key.clone(), position);
+        self.data.push((key, priority));
+        self.sift_up(position);
+
+        None
+    }
+
+    /// Elemento de menor prioridade, sem remover. O(1).
+    pub(crate) fn peek_min(&self) -> Option<(&K, &P)> {
+        self.data.first().map(|(key, priority)| (key, priority))
+    }
+
+    /// Remove e retorna o elemento de menor prioridade. O(log n).
+    pub(crate) fn pop_min(&mut self) -> Option<(K, P)> {
+        match self.is_empty() {
+            true => None,
+            false => Some(self.remove_at(0)),
+        }
+    }
+
+    /// Diminui a prioridade de `key`, retornando a antiga, ou `None` se a chave não estiver
+    /// no heap.
+    ///
+    /// # Panics
+    ///
+    /// Se a nova prioridade for maior que a atual.
+    pub(crate) fn decrease_key<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.positions.get(key)?;
+        assert!(
+            priority <= self.data[position].1,
+            "new priority should be <= the current one"
+        );
+
+        Some(self.replace_at(position, priority))
+    }
+
+    /// Aumenta a prioridade de `key`, retornando a antiga, ou `None` se a chave não estiver
+    /// no heap.
+    ///
+    /// # Panics
+    ///
+    /// Se a nova prioridade for menor que a atual.
+    pub(crate) fn increase_key<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.positions.get(key)?;
+        assert!(
+            priority >= self.data[position].1,
+            "new priority should be >= the current one"
+        );
+
+        Some(self.replace_at(position, priority))
+    }
+
+    /// Remove `key` de qualquer posição do heap, retornando a prioridade dela. O(log n).
+    pub(crate) fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let &position = self.positions.get(key)?;
+        Some(self.remove_at(position).1)
+    }
+
+    /// Pares `(chave, prioridade)` em ordem arbitrária (a ordem interna do heap).
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&K, &P)> {
+        self.data.iter().map(|(key, priority)| (key, priority))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.data.clear();
+        self.positions.clear();
+    }
+
+    // troca a prioridade de quem está em `position` e move o elemento pro lugar certo.
+    fn replace_at(&mut self, position: usize, priority: P) -> P {
+        let old = std::mem::replace(&mut self.data[position].1, priority);
+        match self.data[position].1 < old {
+            true => self.sift_up(position),
+            false => self.sift_down(position),
+        }
+
+        old
+    }
+
+    // o último elemento ocupa o lugar do removido e pode ter que subir ou descer, já que veio
+    // de outro ramo da árvore.
+    fn remove_at(&mut self, position: usize) -> (K, P) {
+        let last = self.data.len() - 1;
+        self.swap(position, last);
+
+        let (key, priority) = self.data.pop().expect("heap should not be empty");
+        self.positions.remove(&key);
+
+        if position < self.data.len() {
+            self.sift_up(position);
+            self.sift_down(position);
+        }
+
+        (key, priority)
+    }
+
+    // toda troca no vetor precisa ser refletida no mapa de posições.
+    fn swap(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+
+        self.data.swap(a, b);
+        *self.positions.get_mut(&self.data[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.data[b].0).unwrap() = b;
+    }
+
+    fn sift_up(&mut self, mut position: usize) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if self.data[position].1 >= self.data[parent].1 {
+                break;
+            }
+
+            self.swap(position, parent);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize) {
+        let length = self.data.len();
+        loop {
+            let mut child = 2 * position + 1;
+            if child >= length {
+                break;
+            }
+
+            if child + 1 < length && self.data[child + 1].1 < self.data[child].1 {
+                child += 1;
+            }
+            if self.data[position].1 <= self.data[child].1 {
+                break;
+            }
+
+            self.swap(position, child);
+            position = child;
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> FromIterator<(K, P)> for IndexedHeap<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Extend<(K, P)> for IndexedHeap<K, P> {
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        for (key, priority) in iter {
+            self.push(key, priority);
+        }
+    }
+}
+
+impl<K: fmt::Debug, P: fmt::Debug> fmt::Debug for IndexedHeap<K, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.data.iter().map(|(key, priority)| (key, priority)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // xorshift simples, só pra gerar entradas reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed % 1000
+            })
+            .collect()
+    }
+
+    // confere a propriedade de heap e se o mapa aponta pras posições certas
+    fn assert_valid<K: Hash + Eq + Clone + fmt::Debug, P: Ord>(heap: &IndexedHeap<K, P>) {
+        assert_eq!(heap.data.len(), heap.positions.len());
+        for (position, (key, priority)) in heap.data.iter().enumerate() {
+            assert_eq!(heap.positions[key], position, "wrong position for {key:?}");
+            if position > 0 {
+                assert!(heap.data[(position - 1) / 2].1 <= *priority);
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_pop_min() {
+        let mut heap = IndexedHeap::new();
+        assert_eq!(heap.pop_min(), None);
+
+        for (key, priority) in [("a", 5), ("b", 2), ("c", 8), ("d", 1)] {
+            assert_eq!(heap.push(key, priority), None);
+        }
+        assert_eq!(heap.len(), 4);
+        assert_eq!(heap.peek_min(), Some((&"d", &1)));
+        assert!(heap.contains("a"));
+        assert_eq!(heap.priority("c"), Some(&8));
+        assert_valid(&heap);
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop_min()).collect();
+        assert_eq!(popped, [("d", 1), ("b", 2), ("a", 5), ("c", 8)]);
+        assert!(!heap.contains("a"));
+    }
+
+    #[test]
+    fn test_push_existing_key_updates_priority() {
+        let mut heap: IndexedHeap<u32, u32> = [(1, 10), (2, 20), (3, 30)].into_iter().collect();
+
+        assert_eq!(heap.push(3, 5), Some(30));
+        assert_eq!(heap.push(1, 40), Some(10));
+        assert_eq!(heap.len(), 3);
+        assert_valid(&heap);
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop_min())
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(order, [3, 2, 1]);
+    }
+
+    #[test]
+    fn test_decrease_and_increase_key() {
+        let mut heap: IndexedHeap<u32, u32> = (0..10).map(|k| (k, 100 + k)).collect();
+
+        assert_eq!(heap.decrease_key(&7, 1), Some(107));
+        assert_eq!(heap.peek_min(), Some((&7, &1)));
+        assert_eq!(heap.increase_key(&7, 500), Some(1));
+        assert_eq!(heap.peek_min(), Some((&0, &100)));
+        assert_valid(&heap);
+
+        assert_eq!(heap.decrease_key(&42, 0), None);
+        assert_eq!(heap.increase_key(&42, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "new priority should be <= the current one")]
+    fn test_decrease_key_with_larger_priority() {
+        let mut heap = IndexedHeap::new();
+        heap.push("a", 1);
+        heap.decrease_key("a", 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "new priority should be >= the current one")]
+    fn test_increase_key_with_smaller_priority() {
+        let mut heap = IndexedHeap::new();
+        heap.push("a", 1);
+        heap.increase_key("a", 0);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut heap: IndexedHeap<u32, u32> = (0..20).map(|k| (k, (k * 7) % 20)).collect();
+
+        assert_eq!(heap.remove(&5), Some(15));
+        assert_eq!(heap.remove(&5), None);
+        assert_eq!(heap.remove(&0), Some(0));
+        assert_eq!(heap.len(), 18);
+        assert_valid(&heap);
+
+        let priorities: Vec<_> = std::iter::from_fn(|| heap.pop_min())
+            .map(|(_, p)| p)
+            .collect();
+        let expected: Vec<u32> = (1..20).filter(|&p| p != 15).collect();
+        assert_eq!(priorities, expected);
+    }
+
+    #[test]
+    fn test_random_operations_against_reference() {
+        // a referência é um mapa chave -> prioridade, e o mínimo é achado na força bruta
+        let mut heap = IndexedHeap::new();
+        let mut reference: HashMap<u64, u64> = HashMap::new();
+        let values = pseudo_random(6000, 31);
+
+        for step in values.chunks(2) {
+            let (key, priority) = (step[0] % 50, step[1]);
+            match step[0] % 4 {
+                0 | 1 => {
+                    assert_eq!(heap.push(key, priority), reference.insert(key, priority));
+                }
+                2 => {
+                    assert_eq!(heap.remove(&key), reference.remove(&key));
+                }
+                _ => {
+                    let popped = heap.pop_min();
+                    let expected_min = reference.values().min().copied();
+                    assert_eq!(popped.as_ref().map(|&(_, p)| p), expected_min);
+                    if let Some((key, _)) = popped {
+                        reference.remove(&key);
+                    }
+                }
+            }
+
+            assert_eq!(heap.len(), reference.len());
+        }
+        assert_valid(&heap);
+    }
+
+    // grafo como lista de adjacência: `graph[u]` tem os pares `(v, peso)`
+    fn dijkstra(graph: &[Vec<(usize, u64)>], source: usize) -> Vec<Option<u64>> {
+        let mut distances = vec![None; graph.len()];
+        let mut queue = IndexedHeap::new();
+        queue.push(source, 0);
+
+        while let Some((u, distance)) = queue.pop_min() {
+            distances[u] = Some(distance);
+            for &(v, weight) in &graph[u] {
+                if distances[v].is_some() {
+                    continue;
+                }
+
+                let candidate = distance + weight;
+                match queue.priority(&v) {
+                    Some(&current) if current <= candidate => {}
+                    Some(_) => {
+                        queue.decrease_key(&v, candidate);
+                    }
+                    None => {
+                        queue.push(v, candidate);
+                    }
+                }
+            }
+        }
+
+        distances
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let nodes = 60;
+        let values = pseudo_random(3 * 400, 57);
+        let mut graph = vec![Vec::new(); nodes];
+        for edge in values.chunks(3) {
+            let (u, v, weight) = (edge[0] as usize % nodes, edge[1] as usize % nodes, edge[2]);
+            graph[u].push((v, weight));
+        }
+
+        // Bellman-Ford como referência: relaxa todas as arestas até nada mudar
+        let mut expected: Vec<Option<u64>> = vec![None; nodes];
+        expected[0] = Some(0);
+        for _ in 0..nodes {
+            for (u, edges) in graph.iter().enumerate() {
+                let Some(distance) = expected[u] else {
+                    continue;
+                };
+                for &(v, weight) in edges {
+                    if expected[v].is_none_or(|current| distance + weight < current) {
+                        expected[v] = Some(distance + weight);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(dijkstra(&graph, 0), expected);
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut heap = IndexedHeap::new();
+        heap.push('x', 2);
+        heap.push('y', 1);
+        assert_eq!(format!("{heap:?}"), "{'y': 1, 'x': 2}");
+    }
+}
@@ -0,0 +1,350 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+/// Leftist heap (min-heap): árvore binária em ordem de heap em que o caminho mais à direita
+/// é sempre o mais curto, com no máximo `log2(n + 1)` nodes.
+///
+/// Toda operação é feita juntando árvores pela espinha direita, então tudo custa O(log n):
+/// `push` junta com uma árvore de um node só e `pop` junta as duas subárvores da raiz.
+///
+/// Com `SKEW = true` vira um skew heap ([`SkewHeap`]): em vez de guardar o rank e só trocar os
+/// filhos quando preciso, ele troca sempre. Sem nenhuma garantia de forma, o custo passa a ser
+/// O(log n) amortizado, mas cada node fica menor e o código, mais simples.
+pub(crate) struct LeftistHeap<T, const SKEW: bool = false> {
+    root: Link<T>,
+    length: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+pub(crate) type SkewHeap<T> = LeftistHeap<T, true>;
+
+// `rank` é o tamanho do caminho mais à direita a partir do node (uma folha tem rank 1, e o
+// `None`, rank 0). a regra do leftist heap é `rank(left) >= rank(right)` em todo node. o
+// `parent` só existe pro `decrease_key`, que precisa cortar um node do meio da árvore.
+struct Node<T> {
+    element: T,
+    left: Link<T>,
+    right: Link<T>,
+    parent: Link<T>,
+    rank: usize,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+/// Referência pra um elemento que está no heap, devolvida pelo `push` e usada no
+/// `decrease_key`.
+pub(crate) struct Handle<T> {
+    node: NonNull<Node<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+unsafe impl<T: Send, const SKEW: bool> Send for LeftistHeap<T, SKEW> {}
+unsafe impl<T: Sync, const SKEW: bool> Sync for LeftistHeap<T, SKEW> {}
+
+fn rank<T>(link: Link<T>) -> usize {
+    link.map_or(0, |node| unsafe { (*node.as_ptr()).rank })
+}
+
+impl<T: Ord, const SKEW: bool> LeftistHeap<T, SKEW> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            root: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// O(log n).
+    pub(crate) fn push(&mut self, element: T) -> Handle<T> {
+        let node = NonNull::from(Box::leak(Box::new(Node {
+            element,
+            left: None,
+            right: None,
+            parent: None,
+            rank: 1,
+        })));
+
+        self.root = unsafe { Self::meld_nodes(self.root, Some(node)) };
+        self.length += 1;
+
+        Handle { node }
+    }
+
+    /// Menor elemento, sem remover. O(1).
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.root.map(|root| unsafe { &(*root.as_ptr()).element })
+    }
+
+    /// Remove e retorna o menor elemento. O(log n).
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+
+        unsafe {
+            let root = Box::from_raw(root.as_ptr());
+            for child in [root.left, root.right].into_iter().flatten() {
+                (*child.as_ptr()).parent = None;
+            }
+
+            self.root = Self::meld_nodes(root.left, root.right);
+            self.length -= 1;
+
+            Some(root.element)
+        }
+    }
+
+    /// Move todos os elementos de `other` pra esse heap. O(log n). Os handles de `other`
+    /// continuam valendo, agora nesse heap.
+    pub(crate) fn meld(&mut self, mut other: Self) {
+        self.root = unsafe { Self::meld_nodes(self.root, other.root.take()) };
+        self.length += std::mem::take(&mut other.length);
+    }
+
+    /// Troca o elemento de `handle` por um menor ou igual. O(log n).
+    ///
+    /// # Safety
+    ///
+    /// `handle` precisa ter vindo de um `push` nesse heap (ou num heap que foi juntado a ele
+    /// com `meld`), e o elemento ainda não pode ter sido removido.
+    ///
+    /// # Panics
+    ///
+    /// Se o novo elemento for maior que o atual.
+    pub(crate) unsafe fn decrease_key(&mut self, handle: Handle<T>, element: T) {
+        let node = handle.node;
+
+        unsafe {
+            assert!(
+                element <= (*node.as_ptr()).element,
+                "new element should be <= the current one"
+            );
+            (*node.as_ptr()).element = element;
+
+            let Some(parent) = (*node.as_ptr()).parent else {
+                return;
+            };
+            if (*parent.as_ptr()).element <= (*node.as_ptr()).element {
+                return;
+            }
+
+            // a subárvore do node é cortada e juntada de novo na raiz.
+            match (*parent.as_ptr()).left == Some(node) {
+                true => (*parent.as_ptr()).left = None,
+                false => (*parent.as_ptr()).right = None,
+            }
+            (*node.as_ptr()).parent = None;
+
+            // o corte pode ter diminuído o rank dos ancestrais, e com isso quebrado a regra em
+            // algum deles. subimos arrumando até um rank não mudar mais.
+            if !SKEW {
+                let mut current = Some(parent);
+                while let Some(ancestor) = current {
+                    let rank = (*ancestor.as_ptr()).rank;
+                    Self::fix(ancestor);
+                    if (*ancestor.as_ptr()).rank == rank {
+                        break;
+                    }
+                    current = (*ancestor.as_ptr()).parent;
+                }
+            }
+
+            self.root = Self::meld_nodes(self.root, Some(node));
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    // junta duas árvores sem pai. a versão recursiva é a mais conhecida
+    // (`a.right = meld(a.right, b)` e depois arrumar `a`), mas num skew heap a espinha direita
+    // pode ficar comprida e estourar a pilha. aqui descemos pela espinha direita trocando de
+    // árvore sempre que a outra tiver a raiz menor, e depois subimos pelos `parent` arrumando
+    // cada node do caminho.
+    unsafe fn meld_nodes(a: Link<T>, b: Link<T>) -> Link<T> {
+        unsafe {
+            let (mut current, mut other) = match (a, b) {
+                (Some(a), Some(b)) => (a, b),
+                (a, None) => return a,
+                (None, b) => return b,
+            };
+
+            if (*other.as_ptr()).element < (*current.as_ptr()).element {
+                std::mem::swap(&mut current, &mut other);
+            }
+            let root = current;
+
+            loop {
+                match (*current.as_ptr()).right {
+                    Some(right) if (*right.as_ptr()).element <= (*other.as_ptr()).element => {
+                        current = right;
+                    }
+                    right => {
+                        (*current.as_ptr()).right = Some(other);
+                        (*other.as_ptr()).parent = Some(current);
+
+                        let Some(right) = right else { break };
+                        current = other;
+                        other = right;
+                    }
+                }
+            }
+
+            let mut node = Some(current);
+            while let Some(current) = node {
+                Self::fix(current);
+                node = (*current.as_ptr()).parent;
+            }
+
+            Some(root)
+        }
+    }
+
+    // restaura a regra no node depois que a subárvore direita mudou.
+    unsafe fn fix(node: NonNull<Node<T>>) {
+        unsafe {
+            let node = node.as_ptr();
+            if SKEW || rank((*node).left) < rank((*node).right) {
+                std::mem::swap(&mut (*node).left, &mut (*node).right);
+            }
+            (*node).rank = rank((*node).right) + 1;
+        }
+    }
+}
+
+// o `Drop` não tem `T: Ord` pra chamar o `pop`, então libera os nodes com uma pilha
+// explícita. sem recursão, porque um skew heap pode ficar com altura n.
+impl<T, const SKEW: bool> Drop for LeftistHeap<T, SKEW> {
+    fn drop(&mut self) {
+        let mut pending: Vector<NonNull<Node<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            pending.extend(node.left);
+            pending.extend(node.right);
+        }
+    }
+}
+
+impl<T: Ord, const SKEW: bool> Default for LeftistHeap<T, SKEW> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const SKEW: bool> FromIterator<T> for LeftistHeap<T, SKEW> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord, const SKEW: bool> Extend<T> for LeftistHeap<T, SKEW> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: fmt::Debug, const SKEW: bool> fmt::Debug for LeftistHeap<T, SKEW> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(if SKEW { "SkewHeap" } else { "LeftistHeap" })
+            .field("len", &self.length)
+            .field(
+                "min",
+                &self.root.map(|root| unsafe { &(*root.as_ptr()).element }),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // confere ordem de heap, ponteiros de pai e (fora do skew heap) a regra dos ranks
+    fn assert_valid<T: Ord, const SKEW: bool>(heap: &LeftistHeap<T, SKEW>) {
+        let mut count = 0;
+        let mut pending: Vec<NonNull<Node<T>>> = heap.root.into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            count += 1;
+            let node = unsafe { &*node.as_ptr() };
+            for child in [node.left, node.right].into_iter().flatten() {
+                let child_ref = unsafe { &*child.as_ptr() };
+                assert!(node.element <= child_ref.element);
+                assert_eq!(
+                    child_ref.parent.map(|p| p.as_ptr().cast_const()),
+                    Some(node as *const _)
+                );
+                pending.push(child);
+            }
+
+            if !SKEW {
+                assert!(rank(node.left) >= rank(node.right));
+                assert_eq!(node.rank, rank(node.right) + 1);
+            }
+        }
+
+        assert_eq!(count, heap.len());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap: LeftistHeap<i32> = [5, 3, 8, 1, 9, 2].into_iter().collect();
+        assert_valid(&heap);
+        assert_eq!(heap.peek(), Some(&1));
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [1, 2, 3, 5, 8, 9]);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_right_spine_is_logarithmic() {
+        let heap: LeftistHeap<u32> = (0..1000).rev().collect();
+        assert_valid(&heap);
+
+        // rank da raiz = tamanho da espinha direita
+        assert!(rank(heap.root) <= (1001f64).log2() as usize);
+    }
+
+    #[test]
+    fn test_decrease_key_fixes_ranks() {
+        let mut heap = LeftistHeap::<u32>::new();
+        let handles: Vec<_> = (0..200).map(|x| heap.push(x * 2 + 10)).collect();
+
+        for (i, &handle) in handles.iter().enumerate().step_by(7) {
+            unsafe { heap.decrease_key(handle, i as u32) };
+            assert_valid(&heap);
+        }
+        assert_eq!(heap.peek(), Some(&0));
+    }
+
+    #[test]
+    fn test_skew_heap() {
+        let mut heap: SkewHeap<u32> = (0..100_000).collect();
+        assert_valid(&heap);
+        assert_eq!(heap.pop(), Some(0));
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(format!("{heap:?}"), "SkewHeap { len: 99998, min: Some(2) }");
+    }
+}
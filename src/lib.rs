@@ -1,2 +1,26 @@
-mod linked_list;
-mod vector;
+pub mod adt;
+pub mod allocator;
+mod arena_list;
+mod binary_heap;
+mod binomial_heap;
+mod deque;
+mod doubly_linked_list;
+mod fibonacci_heap;
+mod hash_map;
+mod indexed_heap;
+mod leftist_heap;
+pub mod linked_list;
+mod mergeable_heap;
+mod min_max_heap;
+mod pairing_heap;
+mod persistent_list;
+mod raw_buffer;
+mod segmented_vector;
+mod small_vector;
+mod unrolled_list;
+pub mod vector;
+
+pub use adt::{Collection, Deque, Queue, Sequence, Stack};
+pub use linked_list::LinkedList;
+pub use raw_buffer::{GrowthPolicy, TryReserveError};
+pub use vector::Vector;
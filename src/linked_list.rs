@@ -1,7 +1,20 @@
 #![allow(unused)]
 
-struct LinkedList<T> {
+use crate::vector::Vector;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+};
+
+pub struct LinkedList<T> {
     head: Link<T>,
+    // guardamos o tamanho pra `len` ser O(1) em vez de percorrer a lista toda.
+    length: usize,
+    // só existe em listas criadas com `with_pool`. fica numa `Box` pra uma lista sem pool
+    // pagar só um ponteiro nulo a mais, e não o pool inteiro.
+    pool: Option<Box<NodePool<T>>>,
 }
 
 struct Node<T> {
@@ -14,33 +27,577 @@ struct Node<T> {
 // os nodes ficam na heap com o ponteiro Box.
 type Link<T> = Option<Box<Node<T>>>;
 
+// guarda a memória de nodes removidos pra reaproveitar nos próximos `push`, evitando um par
+// malloc/free a cada push/pop.
+struct NodePool<T> {
+    // `MaybeUninit` porque o elemento já saiu: só sobrou a memória do node.
+    free: Vector<Box<MaybeUninit<Node<T>>>>,
+    limit: usize,
+    hits: usize,
+    misses: usize,
+}
+
+/// Estatísticas do pool de nodes de uma [`LinkedList`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// `push`es que reaproveitaram um node do pool.
+    pub hits: usize,
+    /// `push`es que precisaram alocar um node novo.
+    pub misses: usize,
+    /// nodes guardados agora, esperando pra serem reaproveitados.
+    pub pooled: usize,
+    pub limit: usize,
+}
+
+impl<T> NodePool<T> {
+    fn with_limit(limit: usize) -> Self {
+        Self {
+            free: Vector::new(),
+            limit,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn allocate(&mut self, element: T, next: Link<T>) -> Box<Node<T>> {
+        let node = Node { element, next };
+
+        match self.free.pop() {
+            Some(memory) => {
+                self.hits += 1;
+                Box::write(memory, node)
+            }
+            None => {
+                self.misses += 1;
+                Box::new(node)
+            }
+        }
+    }
+
+    // tira o elemento e o `next` de dentro do node e, se ainda couber, guarda a memória dele.
+    fn release(&mut self, node: Box<Node<T>>) -> (T, Link<T>) {
+        let raw = Box::into_raw(node);
+        // depois desse `read` o node é só memória: voltamos ele pra um `Box<MaybeUninit>`,
+        // que libera a memória quando é dropado sem tentar dropar o conteúdo de novo.
+        let Node { element, next } = unsafe { raw.read() };
+        let memory = unsafe { Box::from_raw(raw.cast::<MaybeUninit<Node<T>>>()) };
+
+        if self.free.len() < self.limit {
+            self.free.push(memory);
+        }
+
+        (element, next)
+    }
+}
+
+// sem pool, um node é só uma `Box` comum: aloca no push e libera no pop.
+fn allocate<T>(pool: &mut Option<Box<NodePool<T>>>, element: T, next: Link<T>) -> Box<Node<T>> {
+    match pool {
+        Some(pool) => pool.allocate(element, next),
+        None => Box::new(Node { element, next }),
+    }
+}
+
+fn release<T>(pool: &mut Option<Box<NodePool<T>>>, node: Box<Node<T>>) -> (T, Link<T>) {
+    match pool {
+        Some(pool) => pool.release(node),
+        None => {
+            let Node { element, next } = *node;
+            (element, next)
+        }
+    }
+}
+
 impl<T> LinkedList<T> {
-    fn new() -> Self {
-        Self { head: None }
+    pub fn new() -> Self {
+        Self {
+            head: None,
+            length: 0,
+            pool: None,
+        }
+    }
+
+    /// Lista que guarda até `limit` nodes removidos pra reaproveitar em `push`es futuros,
+    /// em vez de liberar e alocar de novo. Vale a pena em loops que fazem push e pop o tempo todo.
+    pub fn with_pool(limit: usize) -> Self {
+        Self {
+            head: None,
+            length: 0,
+            pool: Some(Box::new(NodePool::with_limit(limit))),
+        }
+    }
+
+    /// Estatísticas do pool, ou `None` se a lista não foi criada com [`LinkedList::with_pool`].
+    pub fn pool_stats(&self) -> Option<PoolStats> {
+        self.pool.as_ref().map(|pool| PoolStats {
+            hits: pool.hits,
+            misses: pool.misses,
+            pooled: pool.free.len(),
+            limit: pool.limit,
+        })
+    }
+
+    /// Libera todos os nodes guardados no pool. O limite continua o mesmo.
+    pub fn shrink_pool(&mut self) {
+        if let Some(pool) = &mut self.pool {
+            pool.free.clear();
+            pool.free.shrink_to_fit();
+        }
     }
 
-    fn push(&mut self, element: T) {
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    pub fn push(&mut self, element: T) {
         // esse é um dos tipos de codigos que me faz usar rust.
         // é extremamente simples e faz EXATAMENTE o que foi descrito:
-        // - cria um novo ponteiro na heap
+        // - cria um novo ponteiro na heap (ou reaproveita um do pool)
         // - cria um node novo com o elemento passado
         // - MOVEMOS o ponteiro da head para esse novo node com `take()`, que toma a
         // propriedade do antigo ponteiro
         // - movemos a head para o novo node
-        let new_node = Box::new(Node {
-            element,
-            next: self.head.take(),
-        });
+        let new_node = allocate(&mut self.pool, element, self.head.take());
 
         self.head = Some(new_node);
+        self.length += 1;
     }
 
-    fn pop(&mut self) -> Option<T> {
+    pub fn pop(&mut self) -> Option<T> {
         self.head.take().map(|node| {
-            self.head = node.next;
-            node.element
+            let (element, next) = release(&mut self.pool, node);
+            self.head = next;
+            self.length -= 1;
+            element
         })
     }
+
+    /// Olha o elemento da cabeça sem remover.
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.element)
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.element)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+
+    pub fn contains(&self, element: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|current| current == element)
+    }
+
+    /// Inverte a ordem da lista sem alocar nada, só trocando os ponteiros `next`.
+    pub fn reverse(&mut self) {
+        // a cada passo tiramos o node da frente de `current` e colocamos ele
+        // na frente de `reversed`, igual a desempilhar de uma pilha e empilhar em outra.
+        let mut reversed: Link<T> = None;
+        let mut current = self.head.take();
+
+        while let Some(mut node) = current {
+            current = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+
+        self.head = reversed;
+    }
+
+    // devolve o `next` do node na posição `index - 1` (ou a `head`, se `index == 0`).
+    // ou seja, o "buraco" onde o node `index` está pendurado.
+    fn link_at(&mut self, index: usize) -> &mut Link<T> {
+        let mut cursor = &mut self.head;
+
+        for _ in 0..index {
+            cursor = &mut cursor.as_mut().expect("index within bounds").next;
+        }
+
+        cursor
+    }
+
+    /// Move todos os elementos de `other` pro final dessa lista. O(len), já que não temos
+    /// ponteiro pro final.
+    pub fn append(&mut self, other: &mut Self) {
+        let tail = self.link_at(self.length);
+        *tail = other.head.take();
+
+        self.length += other.length;
+        other.length = 0;
+    }
+
+    /// Divide a lista em duas: `self` fica com os `at` primeiros elementos e o resto é devolvido.
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let length = self.length;
+        assert!(
+            at <= length,
+            "cannot split off at a nonexistent index (is {at}, len is {length})"
+        );
+
+        // a parte cortada vira uma lista nova, sem pool: os nodes guardados ficam com `self`.
+        let mut rest = Self::new();
+        rest.head = self.link_at(at).take();
+        rest.length = length - at;
+        self.length = at;
+
+        rest
+    }
+
+    /// Remove e retorna o primeiro elemento que satisfaz `predicate`.
+    pub fn remove_first<F: FnMut(&T) -> bool>(&mut self, mut predicate: F) -> Option<T> {
+        let mut cursor = &mut self.head;
+
+        // anda até o buraco do primeiro node que satisfaz o predicado (ou até o final).
+        while cursor
+            .as_ref()
+            .is_some_and(|node| !predicate(&node.element))
+        {
+            cursor = &mut cursor.as_mut()?.next;
+        }
+
+        let (element, next) = release(&mut self.pool, cursor.take()?);
+        *cursor = next;
+        self.length -= 1;
+
+        Some(element)
+    }
+
+    /// Mantém só os elementos em que `keep` devolve `true`, numa passada só.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        let mut cursor = &mut self.head;
+
+        // o `keep` roda com o node ainda ligado na lista: se ele entrar em pânico, a lista
+        // continua inteira e com o `length` certo.
+        while let Some(kept) = cursor.as_ref().map(|node| keep(&node.element)) {
+            if kept {
+                cursor = &mut cursor.as_mut().expect("checked above").next;
+            } else if let Some(node) = cursor.take() {
+                // pula o node: o `next` dele passa a ocupar o buraco, e o elemento
+                // é dropado aqui, sozinho, sem recursão.
+                let (element, next) = release(&mut self.pool, node);
+                *cursor = next;
+                self.length -= 1;
+                drop(element);
+            }
+        }
+    }
+}
+
+// algoritmos: ordenação, merge, dedup e rotação. nenhum deles aloca nem é recursivo,
+// todos só trocam os ponteiros `next` dos nodes que já existem.
+impl<T> LinkedList<T> {
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        self.sort_by(|a, b| key(a).cmp(&key(b)));
+    }
+
+    /// Ordena a lista com um merge sort bottom-up estável. O(n log n) comparações,
+    /// sem alocar nada: os nodes só são religados.
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        // funciona como um contador binário: `bins[i]` é vazio ou uma sublista ordenada com
+        // exatamente 2^i elementos. cada node novo entra como uma sublista de tamanho 1 e vai
+        // "subindo" (fundindo com o bin ocupado) igual ao vai-um de uma soma binária.
+        let mut state = SortState::new(&mut self.head);
+
+        while let Some(mut node) = state.remaining.take() {
+            state.remaining = node.next.take();
+            state.carry = Some(node);
+
+            for bin in &mut state.bins {
+                if bin.is_none() {
+                    *bin = state.carry.take();
+                    break;
+                }
+
+                // os elementos do bin vieram antes dos do `carry`, então eles vão na
+                // esquerda do merge pra manter a ordenação estável.
+                merge_links(bin, &mut state.carry, &mut state.merged, &mut compare);
+                state.carry = state.merged.take();
+            }
+        }
+
+        // bins mais altos guardam elementos mais antigos, então juntamos de baixo pra cima
+        // sempre colocando o bin atual na esquerda.
+        for bin in &mut state.bins {
+            merge_links(bin, &mut state.carry, &mut state.merged, &mut compare);
+            state.carry = state.merged.take();
+        }
+
+        // só sobrou o `carry`, com a lista inteira ordenada: o drop do `state` devolve ele
+        // pra `self.head`.
+    }
+
+    /// Junta `other`, que precisa estar ordenada, nessa lista (também ordenada), deixando
+    /// `other` vazia. Em caso de empate, os elementos de `self` vêm primeiro. O(n + m).
+    pub fn merge(&mut self, other: &mut Self)
+    where
+        T: Ord,
+    {
+        // os nodes de `other` passam a contar como nossos antes de comparar qualquer coisa:
+        // se o `cmp` entrar em pânico, o `state` devolve todos eles pra `self`.
+        self.length += other.length;
+        other.length = 0;
+
+        let mut state = SortState::new(&mut self.head);
+        state.carry = other.head.take();
+        merge_links(
+            &mut state.remaining,
+            &mut state.carry,
+            &mut state.merged,
+            &mut T::cmp,
+        );
+    }
+
+    /// Remove elementos consecutivos repetidos. Numa lista ordenada, remove todas as repetições.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|current, previous| current == previous)
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|current, previous| key(current) == key(previous))
+    }
+
+    /// Remove elementos consecutivos para os quais `same_bucket(atual, anterior)` devolve `true`.
+    /// O "anterior" é sempre o último elemento que ficou na lista. O(n).
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        let mut cursor = self.head.as_deref_mut();
+
+        while let Some(node) = cursor {
+            // enquanto o vizinho for repetido, pula ele. o `node` continua sendo o "anterior".
+            while node
+                .next
+                .as_mut()
+                .is_some_and(|next| same_bucket(&mut next.element, &mut node.element))
+            {
+                if let Some(duplicate) = node.next.take() {
+                    // o elemento só é dropado depois da lista estar consistente de novo: se
+                    // o drop dele entrar em pânico, o resto continua ligado no `node`.
+                    let (element, next) = release(&mut self.pool, duplicate);
+                    node.next = next;
+                    self.length -= 1;
+                    drop(element);
+                }
+            }
+
+            cursor = node.next.as_deref_mut();
+        }
+    }
+
+    /// Elemento do meio, na posição `len / 2`. Em listas de tamanho par é o segundo dos dois
+    /// do meio. Como já sabemos o tamanho, basta andar `len / 2` nodes (sem precisar do truque
+    /// da lebre e da tartaruga).
+    pub fn middle(&self) -> Option<&T> {
+        self.iter().nth(self.length / 2)
+    }
+
+    pub fn middle_mut(&mut self) -> Option<&mut T> {
+        let middle = self.length / 2;
+        self.iter_mut().nth(middle)
+    }
+
+    /// Move os `mid` primeiros elementos pro final. O(len), sem alocar.
+    pub fn rotate_left(&mut self, mid: usize) {
+        let length = self.length;
+        assert!(
+            mid <= length,
+            "rotation amount (is {mid}) should be <= len (is {length})"
+        );
+
+        if mid == 0 || mid == length {
+            return;
+        }
+
+        // [a b | c d e] -> corta em `mid` e pendura a primeira parte no final da segunda.
+        // trocamos só os nodes (e não a lista inteira) pra `self` continuar com o pool dele.
+        let mut front = self.split_off(mid);
+        std::mem::swap(&mut self.head, &mut front.head);
+        std::mem::swap(&mut self.length, &mut front.length);
+        self.append(&mut front);
+    }
+
+    /// Move os `k` últimos elementos pro começo. O(len), sem alocar.
+    pub fn rotate_right(&mut self, k: usize) {
+        let length = self.length;
+        assert!(
+            k <= length,
+            "rotation amount (is {k}) should be <= len (is {length})"
+        );
+
+        self.rotate_left(length - k);
+    }
+}
+
+// nodes de uma lista no meio de um sort ou merge, espalhados em vários pedaços. nada fica em
+// variável local: se o `compare` entrar em pânico, o drop religa todos os pedaços em `head`
+// (fora de ordem, mas sem perder nem vazar nenhum node), então o `length` continua certo.
+// no caminho normal só sobra um pedaço, a lista ordenada, e o drop só coloca ela no lugar.
+struct SortState<'a, T> {
+    head: &'a mut Link<T>,
+    // como a lista nunca passa de `usize::MAX` elementos, `usize::BITS` bins bastam.
+    bins: [Link<T>; usize::BITS as usize],
+    remaining: Link<T>,
+    carry: Link<T>,
+    merged: Link<T>,
+}
+
+impl<'a, T> SortState<'a, T> {
+    fn new(head: &'a mut Link<T>) -> Self {
+        Self {
+            remaining: head.take(),
+            head,
+            bins: [const { None }; usize::BITS as usize],
+            carry: None,
+            merged: None,
+        }
+    }
+}
+
+impl<T> Drop for SortState<'_, T> {
+    fn drop(&mut self) {
+        let pieces = [&mut self.merged, &mut self.carry, &mut self.remaining]
+            .into_iter()
+            .chain(&mut self.bins);
+
+        // pendura cada pedaço no final do anterior, andando iterativamente até o fim dele.
+        let mut tail = &mut *self.head;
+        for piece in pieces {
+            *tail = piece.take();
+
+            while tail.is_some() {
+                tail = &mut tail.as_mut().expect("checked above").next;
+            }
+        }
+    }
+}
+
+// funde duas sequências de nodes já ordenadas em `merged` (que começa vazio), esvaziando
+// `left` e `right`. nos empates o node de `left` vem primeiro, o que deixa o merge (e o sort)
+// estável. os três pedaços são do chamador, então um pânico no `compare` não perde nada.
+fn merge_links<T, F: FnMut(&T, &T) -> Ordering>(
+    left: &mut Link<T>,
+    right: &mut Link<T>,
+    merged: &mut Link<T>,
+    compare: &mut F,
+) {
+    // sempre aponta pro buraco no final da lista resultante, como no `FromIterator`.
+    let mut tail = merged;
+
+    while let (Some(l), Some(r)) = (&*left, &*right) {
+        let source = match compare(&r.element, &l.element) {
+            Ordering::Less => &mut *right,
+            _ => &mut *left,
+        };
+
+        if let Some(mut node) = source.take() {
+            *source = node.next.take();
+            tail = &mut tail.insert(node).next;
+        }
+    }
+
+    // uma das duas acabou; o que sobrou da outra já está ordenado e vai inteiro pro final.
+    *tail = left.take().or_else(|| right.take());
+}
+
+/// Iterador que empresta os elementos da lista, da cabeça até o final.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.element
+        })
+    }
+}
+
+/// Iterador que empresta os elementos da lista de forma mutável.
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        // `&mut` não é `Copy` como o `&`, então precisamos tirar ele do `Option` com `take`.
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.element
+        })
+    }
+}
+
+/// Iterador que consome a lista, entregando os elementos da cabeça até o final.
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
 }
 
 impl<T> Drop for LinkedList<T> {
@@ -55,6 +612,95 @@ impl<T> Drop for LinkedList<T> {
     }
 }
 
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        // como só temos ponteiro pra cabeça, guardamos uma referência pro último `next`
+        // e vamos pendurando os nodes novos nele. assim a ordem do iterador é preservada.
+        let mut tail = &mut list.head;
+
+        for element in iter {
+            let node = tail.insert(Box::new(Node {
+                element,
+                next: None,
+            }));
+            tail = &mut node.next;
+            list.length += 1;
+        }
+
+        list
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        // se algum clone der panic, a lista parcial é dropada pelo nosso `Drop` iterativo.
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // igual ao slice, incluímos o tamanho no hash. sem isso, listas aninhadas
+        // como [[1], [2]] e [[1, 2]] poderiam gerar a mesma sequência de bytes.
+        state.write_usize(self.length);
+        for element in self.iter() {
+            element.hash(state);
+        }
+    }
+}
+
+/// Cria uma [`LinkedList`] com os elementos passados.
+///
+/// A cabeça da lista é o primeiro elemento, então `list![1, 2, 3].pop()` devolve `Some(1)`.
+/// `list![x; n]` repete `x` `n` vezes.
+#[macro_export]
+macro_rules! list {
+    () => {
+        $crate::linked_list::LinkedList::new()
+    };
+    ($element:expr; $n:expr) => {
+        ::std::iter::repeat_n($element, $n).collect::<$crate::linked_list::LinkedList<_>>()
+    };
+    ($($element:expr),+ $(,)?) => {
+        <$crate::linked_list::LinkedList<_> as ::std::iter::FromIterator<_>>::from_iter([$($element),+])
+    };
+}
+
+pub(crate) use list;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +726,533 @@ mod tests {
         assert_eq!(list.pop(), Some(1));
         assert_eq!(list.pop(), None);
     }
+
+    #[test]
+    fn test_macro_and_from_iterator() {
+        let mut list = list![1, 2, 3];
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(list.pop(), None);
+
+        let empty: LinkedList<i32> = list![];
+        assert_eq!(empty, LinkedList::default());
+
+        let repeated = list![String::from("a"); 2];
+        assert_eq!(repeated, list![String::from("a"), String::from("a")]);
+
+        let collected: LinkedList<_> = (0..4).collect();
+        assert_eq!(collected, list![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_clone_and_debug() {
+        let list = list![String::from("a"), String::from("b")];
+        let mut cloned = list.clone();
+
+        assert_eq!(list, cloned);
+        assert_eq!(format!("{:?}", cloned), r#"["a", "b"]"#);
+
+        cloned.pop();
+        assert_ne!(list, cloned);
+        assert_eq!(format!("{:?}", list), r#"["a", "b"]"#);
+    }
+
+    #[test]
+    fn test_comparisons_and_hash() {
+        use std::collections::{HashSet, hash_map::DefaultHasher};
+
+        let a = list![1, 2, 3];
+        let b = list![1, 3];
+        let c = list![1, 2];
+
+        assert!(a < b);
+        assert!(c < a);
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+
+        let hash = |list: &LinkedList<i32>| {
+            let mut hasher = DefaultHasher::new();
+            list.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&a.clone()));
+        assert_ne!(hash(&a), hash(&c));
+
+        let set: HashSet<_> = [a.clone(), b, a].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    // grande o suficiente pra estourar a stack se alguma operação fosse recursiva
+    const HUGE: usize = 1_000_000;
+
+    #[test]
+    fn test_peek_and_len() {
+        let mut list = LinkedList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.peek(), None);
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.peek(), Some(&2));
+
+        if let Some(head) = list.peek_mut() {
+            *head *= 10;
+        }
+        assert_eq!(list.pop(), Some(20));
+        assert_eq!(list.len(), 1);
+        assert!(!list.is_empty());
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut list = list![String::from("a"), String::from("b"), String::from("c")];
+
+        for s in list.iter_mut() {
+            s.push('!');
+        }
+        for s in &mut list {
+            s.push('?');
+        }
+
+        let borrowed: Vec<&str> = list.iter().map(String::as_str).collect();
+        assert_eq!(borrowed, ["a!?", "b!?", "c!?"]);
+
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.len(), 3);
+        assert_eq!(into_iter.next().as_deref(), Some("a!?"));
+        assert_eq!(into_iter.collect::<Vec<_>>(), ["b!?", "c!?"]);
+    }
+
+    #[test]
+    fn test_reverse_and_contains() {
+        let mut list = list![1, 2, 3, 4];
+        list.reverse();
+        assert_eq!(list, list![4, 3, 2, 1]);
+        assert!(list.contains(&3));
+        assert!(!list.contains(&5));
+
+        let mut empty: LinkedList<i32> = list![];
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_append_and_split_off() {
+        let mut a = list![1, 2];
+        let mut b = list![3, 4, 5];
+        a.append(&mut b);
+
+        assert_eq!(a, list![1, 2, 3, 4, 5]);
+        assert_eq!(a.len(), 5);
+        assert!(b.is_empty());
+        assert_eq!(b.len(), 0);
+
+        let tail = a.split_off(3);
+        assert_eq!(a, list![1, 2, 3]);
+        assert_eq!(tail, list![4, 5]);
+        assert_eq!(tail.len(), 2);
+
+        let everything = a.split_off(0);
+        assert!(a.is_empty());
+        assert_eq!(everything.len(), 3);
+
+        let nothing = b.split_off(0);
+        assert!(nothing.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot split off at a nonexistent index (is 3, len is 2)")]
+    fn test_split_off_out_of_bounds() {
+        list![1, 2].split_off(3);
+    }
+
+    #[test]
+    fn test_remove_first_and_retain() {
+        let mut list: LinkedList<i32> = (0..10).collect();
+
+        assert_eq!(list.remove_first(|n| *n > 4), Some(5));
+        assert_eq!(list.remove_first(|n| *n == 0), Some(0));
+        assert_eq!(list.remove_first(|n| *n > 100), None);
+        assert_eq!(list.len(), 8);
+
+        list.retain(|n| n % 2 == 0);
+        assert_eq!(list, list![2, 4, 6, 8]);
+        assert_eq!(list.len(), 4);
+
+        list.retain(|_| false);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_huge_list_without_recursion() {
+        let mut list: LinkedList<usize> = (0..HUGE).collect();
+        assert_eq!(list.len(), HUGE);
+
+        list.reverse();
+        assert_eq!(list.peek(), Some(&(HUGE - 1)));
+
+        list.retain(|n| n % 2 == 0);
+        assert_eq!(list.len(), HUGE / 2);
+
+        let mut tail = list.split_off(HUGE / 4);
+        assert_eq!(tail.len(), HUGE / 4);
+
+        list.append(&mut tail);
+        assert_eq!(list.len(), HUGE / 2);
+        assert!(list.contains(&0));
+        assert_eq!(list.remove_first(|n| *n == 0), Some(0));
+
+        let cloned = list.clone();
+        assert_eq!(cloned, list);
+        assert_eq!(list.into_iter().count(), HUGE / 2 - 1);
+    }
+
+    #[test]
+    fn test_retain_panic_keeps_list_consistent() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let mut list: LinkedList<usize> = (0..HUGE).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.retain(|n| {
+                assert!(*n < HUGE / 2, "boom");
+                n % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+
+        // os nodes antes do pânico já foram filtrados; o resto continua ligado na lista
+        let expected = (0..HUGE).filter(|n| *n >= HUGE / 2 || n % 2 == 0);
+        assert_eq!(list.len(), HUGE / 4 + HUGE / 2);
+        assert!(list.iter().copied().eq(expected));
+        drop(list);
+    }
+
+    // xorshift simples, só pra gerar entradas grandes e reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut list = list![5, 1, 4, 2, 3];
+        list.sort();
+        assert_eq!(list, list![1, 2, 3, 4, 5]);
+
+        list.sort_by(|a, b| b.cmp(a));
+        assert_eq!(list, list![5, 4, 3, 2, 1]);
+
+        let mut empty: LinkedList<i32> = list![];
+        empty.sort();
+        assert!(empty.is_empty());
+
+        let mut single = list![1];
+        single.sort();
+        assert_eq!(single, list![1]);
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        // ordena só pela chave; os números de ordem dentro de cada chave precisam continuar
+        // crescentes.
+        let values = pseudo_random(1000, 42);
+        let mut list: LinkedList<(u64, usize)> = values
+            .iter()
+            .enumerate()
+            .map(|(order, value)| (value % 10, order))
+            .collect();
+
+        list.sort_by_key(|&(key, _)| key);
+
+        let sorted: Vec<_> = list.iter().copied().collect();
+        let mut expected = sorted.clone();
+        expected.sort();
+        assert_eq!(sorted, expected);
+        assert_eq!(list.len(), 1000);
+    }
+
+    #[test]
+    fn test_sort_huge_list() {
+        let values = pseudo_random(HUGE, 7);
+        let mut list: LinkedList<u64> = values.iter().copied().collect();
+        list.sort();
+
+        let mut expected = values;
+        expected.sort();
+        assert!(list.iter().eq(expected.iter()));
+        assert_eq!(list.len(), HUGE);
+
+        // entradas já ordenadas e invertidas são os casos clássicos que quebram sorts ingênuos
+        let mut ascending: LinkedList<usize> = (0..HUGE).collect();
+        ascending.sort();
+        assert!(ascending.iter().copied().eq(0..HUGE));
+
+        ascending.sort_by(|a, b| b.cmp(a));
+        assert!(ascending.iter().copied().eq((0..HUGE).rev()));
+    }
+
+    #[test]
+    fn test_sort_panic_keeps_every_node() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        let values = pseudo_random(HUGE, 3);
+        let mut list: LinkedList<u64> = values.iter().copied().collect();
+
+        // entra em pânico no meio do sort, com nodes espalhados pelos bins e num merge
+        let mut comparisons = 0;
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            list.sort_by(|a, b| {
+                comparisons += 1;
+                assert!(comparisons < HUGE * 3, "boom");
+                a.cmp(b)
+            })
+        }));
+        assert!(result.is_err());
+
+        // a ordem fica qualquer, mas nenhum node some e a lista continua utilizável
+        assert_eq!(list.len(), HUGE);
+        assert_eq!(list.iter().count(), HUGE);
+        list.sort();
+        let mut expected = values;
+        expected.sort();
+        assert!(list.iter().eq(expected.iter()));
+
+        let mut evens: LinkedList<Bomb> = (0..HUGE).step_by(2).map(Bomb).collect();
+        let mut odds: LinkedList<Bomb> = (1..HUGE).step_by(2).map(Bomb).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| evens.merge(&mut odds)));
+        assert!(result.is_err());
+        assert_eq!(evens.len(), HUGE);
+        assert_eq!(evens.iter().count(), HUGE);
+        assert!(odds.is_empty());
+
+        // compara normalmente, mas entra em pânico ao chegar no meio da lista
+        #[derive(PartialEq, Eq)]
+        struct Bomb(usize);
+
+        impl PartialOrd for Bomb {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for Bomb {
+            fn cmp(&self, other: &Self) -> Ordering {
+                assert!(self.0 < HUGE / 2, "boom");
+                self.0.cmp(&other.0)
+            }
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut evens: LinkedList<usize> = (0..HUGE).step_by(2).collect();
+        let mut odds: LinkedList<usize> = (1..HUGE).step_by(2).collect();
+
+        evens.merge(&mut odds);
+        assert_eq!(evens.len(), HUGE);
+        assert!(odds.is_empty());
+        assert!(evens.iter().copied().eq(0..HUGE));
+
+        let mut left = list![1, 3, 3, 7];
+        let mut right = list![0, 3, 4, 8, 9];
+        left.merge(&mut right);
+        assert_eq!(left, list![0, 1, 3, 3, 3, 4, 7, 8, 9]);
+        assert_eq!(left.len(), 9);
+
+        let mut empty = list![];
+        empty.merge(&mut list![1, 2]);
+        assert_eq!(empty, list![1, 2]);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut list = list![1, 1, 2, 3, 3, 3, 1, 4, 4];
+        list.dedup();
+        assert_eq!(list, list![1, 2, 3, 1, 4]);
+        assert_eq!(list.len(), 5);
+
+        let mut words = list!["a", "A", "b", "B", "b"];
+        words.dedup_by_key(|s| s.to_lowercase());
+        assert_eq!(words, list!["a", "b"]);
+
+        // numa lista ordenada, sobra só um de cada
+        let mut huge: LinkedList<usize> = (0..HUGE).map(|n| n / 4).collect();
+        huge.dedup();
+        assert_eq!(huge.len(), HUGE / 4);
+        assert!(huge.iter().copied().eq(0..HUGE / 4));
+    }
+
+    #[test]
+    fn test_dedup_panic_keeps_list_consistent() {
+        use std::panic::{AssertUnwindSafe, catch_unwind};
+
+        // o drop do duplicado entra em pânico com o resto da lista (grande) ainda pendurado
+        let mut list: LinkedList<Bomb> = (0..HUGE).map(|n| Bomb(n / 2, n == 1)).collect();
+        let result = catch_unwind(AssertUnwindSafe(|| list.dedup()));
+        assert!(result.is_err());
+
+        assert_eq!(list.len(), HUGE - 1);
+        assert_eq!(list.iter().count(), HUGE - 1);
+        list.dedup();
+        assert_eq!(list.len(), HUGE / 2);
+        assert!(list.iter().map(|bomb| bomb.0).eq(0..HUGE / 2));
+
+        // compara só pelo número; explode no drop se o `bool` for `true`
+        struct Bomb(usize, bool);
+
+        impl PartialEq for Bomb {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                assert!(!self.1, "boom");
+            }
+        }
+    }
+
+    #[test]
+    fn test_middle() {
+        assert_eq!(LinkedList::<i32>::new().middle(), None);
+        assert_eq!(list![1].middle(), Some(&1));
+        assert_eq!(list![1, 2, 3].middle(), Some(&2));
+        assert_eq!(list![1, 2, 3, 4].middle(), Some(&3));
+
+        let mut list: LinkedList<usize> = (0..HUGE).collect();
+        assert_eq!(list.middle(), Some(&(HUGE / 2)));
+        *list.middle_mut().unwrap() = 0;
+        assert_eq!(list.middle(), Some(&0));
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut list = list![1, 2, 3, 4, 5];
+        list.rotate_left(2);
+        assert_eq!(list, list![3, 4, 5, 1, 2]);
+        list.rotate_right(2);
+        assert_eq!(list, list![1, 2, 3, 4, 5]);
+
+        list.rotate_left(0);
+        list.rotate_right(5);
+        assert_eq!(list, list![1, 2, 3, 4, 5]);
+        assert_eq!(list.len(), 5);
+
+        let mut huge: LinkedList<usize> = (0..HUGE).collect();
+        huge.rotate_left(HUGE / 3);
+        assert_eq!(huge.peek(), Some(&(HUGE / 3)));
+        huge.rotate_right(HUGE / 3);
+        assert!(huge.iter().copied().eq(0..HUGE));
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation amount (is 4) should be <= len (is 3)")]
+    fn test_rotate_out_of_bounds() {
+        list![1, 2, 3].rotate_left(4);
+    }
+
+    #[test]
+    fn test_node_pool() {
+        let mut list = LinkedList::with_pool(2);
+        for i in 0..4 {
+            list.push(i);
+        }
+        assert_eq!(
+            list.pool_stats(),
+            Some(PoolStats {
+                hits: 0,
+                misses: 4,
+                pooled: 0,
+                limit: 2
+            })
+        );
+
+        // só cabem 2 no pool, os outros 2 nodes são liberados
+        for _ in 0..4 {
+            list.pop();
+        }
+        assert_eq!(list.pool_stats().unwrap().pooled, 2);
+
+        // os dois primeiros pushes reaproveitam, o terceiro precisa alocar
+        list.push(10);
+        list.push(11);
+        list.push(12);
+        let stats = list.pool_stats().unwrap();
+        assert_eq!((stats.hits, stats.misses, stats.pooled), (2, 5, 0));
+        assert_eq!(list, list![12, 11, 10]);
+
+        // remoções no meio também devolvem nodes pro pool
+        list.retain(|n| *n != 11);
+        list.remove_first(|n| *n == 10);
+        assert_eq!(list.pool_stats().unwrap().pooled, 2);
+
+        list.shrink_pool();
+        assert_eq!(list.pool_stats().unwrap().pooled, 0);
+        assert_eq!(list.pool_stats().unwrap().limit, 2);
+        assert_eq!(list, list![12]);
+    }
+
+    #[test]
+    fn test_pool_hot_loop() {
+        let mut list = LinkedList::with_pool(16);
+
+        for round in 0..1000 {
+            for i in 0..16 {
+                list.push(round * 16 + i);
+            }
+            for _ in 0..16 {
+                list.pop();
+            }
+        }
+
+        // só a primeira rodada aloca
+        let stats = list.pool_stats().unwrap();
+        assert_eq!(stats.misses, 16);
+        assert_eq!(stats.hits, 16 * 999);
+
+        // sem pool não tem estatística nenhuma, e a lista só cresce um ponteiro
+        let mut plain = LinkedList::new();
+        plain.push(1);
+        plain.pop();
+        plain.push(2);
+        plain.shrink_pool();
+        assert_eq!(plain.pool_stats(), None);
+        assert_eq!(
+            std::mem::size_of::<LinkedList<u64>>(),
+            3 * std::mem::size_of::<usize>()
+        );
+    }
+
+    #[test]
+    fn test_pool_drops_elements_once() {
+        use std::rc::Rc;
+
+        let tracker = Rc::new(());
+        let mut list = LinkedList::with_pool(8);
+        for _ in 0..6 {
+            list.push(Rc::clone(&tracker));
+        }
+
+        // elementos que saem pelo pool são dropados na hora; a memória guardada não tem mais
+        // elemento nenhum pra dropar.
+        drop(list.pop());
+        list.retain(|_| false);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+        assert_eq!(list.pool_stats().unwrap().pooled, 6);
+
+        list.push(Rc::clone(&tracker));
+        list.push(Rc::clone(&tracker));
+        list.rotate_left(1);
+        assert_eq!(list.pool_stats().unwrap().limit, 8);
+        drop(list);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
 }
@@ -0,0 +1,391 @@
+#![allow(unused)]
+
+// heaps "juntáveis": além das operações de uma fila de prioridade, sabem juntar dois heaps
+// inteiros (`meld`) rápido e diminuir um elemento que já está lá dentro, apontado por um
+// handle. o `BinaryHeap` num vetor precisaria copiar um dos dois heaps pra juntar, então
+// todas essas estruturas são feitas de nodes ligados por ponteiros.
+//
+// | heap      | push        | pop             | meld        | decrease_key    |
+// |-----------|-------------|-----------------|-------------|-----------------|
+// | pairing   | O(1)        | O(log n) amort. | O(1)        | O(log n) amort. |
+// | leftist   | O(log n)    | O(log n)        | O(log n)    | O(log n)        |
+// | skew      | O(log n) a. | O(log n) amort. | O(log n) a. | O(log n) amort. |
+// | binomial  | O(log n)    | O(log n)        | O(log n)    | O(log n)        |
+// | fibonacci | O(1)        | O(log n) amort. | O(1)        | O(1) amort.     |
+
+use crate::{
+    binomial_heap::{self, BinomialHeap},
+    fibonacci_heap::{self, FibonacciHeap},
+    leftist_heap::{self, LeftistHeap},
+    pairing_heap::{self, PairingHeap},
+};
+
+/// Min-heap que pode ser juntado com outro do mesmo tipo e que permite diminuir um elemento
+/// através do handle devolvido pelo `push`.
+pub(crate) trait MergeableHeap<T: Ord>: Default {
+    /// Aponta pra um elemento enquanto ele estiver no heap.
+    type Handle: Copy;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle;
+
+    /// Menor elemento, sem remover.
+    fn peek(&self) -> Option<&T>;
+
+    /// Remove e retorna o menor elemento.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Move todos os elementos de `other` pra esse heap. Os handles de `other` passam a valer
+    /// nesse heap.
+    fn meld(&mut self, other: Self);
+
+    /// Troca o elemento de `handle` por um menor ou igual. Entra em pânico se ele for maior.
+    ///
+    /// # Safety
+    ///
+    /// `handle` precisa ter vindo de um `push` nesse heap (ou num heap que foi juntado a ele
+    /// com `meld`), e o elemento ainda não pode ter sido removido.
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, element: T);
+}
+
+// como nas traits do `adt`, os métodos próprios têm prioridade, então `self.push(...)` chama
+// a implementação concreta. as duas variantes do `LeftistHeap` (leftist e skew) saem do
+// mesmo impl.
+
+impl<T: Ord> MergeableHeap<T> for PairingHeap<T> {
+    type Handle = pairing_heap::Handle<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle {
+        self.push(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, element: T) {
+        unsafe { self.decrease_key(handle, element) }
+    }
+}
+
+impl<T: Ord, const SKEW: bool> MergeableHeap<T> for LeftistHeap<T, SKEW> {
+    type Handle = leftist_heap::Handle<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle {
+        self.push(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, element: T) {
+        unsafe { self.decrease_key(handle, element) }
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for BinomialHeap<T> {
+    type Handle = binomial_heap::Handle<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle {
+        self.push(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, element: T) {
+        unsafe { self.decrease_key(handle, element) }
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for FibonacciHeap<T> {
+    type Handle = fibonacci_heap::Handle<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, element: T) -> Self::Handle {
+        self.push(element)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+
+    unsafe fn decrease_key(&mut self, handle: Self::Handle, element: T) {
+        unsafe { self.decrease_key(handle, element) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::leftist_heap::SkewHeap;
+    use std::{
+        cell::Cell,
+        collections::{BTreeSet, HashMap},
+        rc::Rc,
+    };
+
+    // xorshift simples, só pra gerar entradas reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            })
+            .collect()
+    }
+
+    // a bateria abaixo é a mesma pra todo heap, e o macro no final roda ela pra cada um.
+    // os elementos são pares `(prioridade, id)`: o id deixa todos distintos, então dá pra
+    // saber exatamente quem saiu do heap e qual handle deixou de valer.
+
+    fn against_reference<H: MergeableHeap<(u64, usize)>>(seed: u64) {
+        let mut heap = H::default();
+        let mut reference = BTreeSet::new();
+        let mut handles: HashMap<usize, (H::Handle, u64)> = HashMap::new();
+        let mut next_id = 0;
+
+        let mut push = |heap: &mut H, priority: u64, handles: &mut HashMap<_, _>| {
+            let id = next_id;
+            next_id += 1;
+            handles.insert(id, (heap.push((priority, id)), priority));
+            (priority, id)
+        };
+
+        for random in pseudo_random(4000, seed) {
+            let priority = random % 1000;
+            match random % 10 {
+                0..=3 => {
+                    reference.insert(push(&mut heap, priority, &mut handles));
+                }
+                4..=6 => {
+                    let popped = heap.pop();
+                    assert_eq!(popped, reference.pop_first());
+                    if let Some((_, id)) = popped {
+                        handles.remove(&id);
+                    }
+                }
+                7 | 8 => {
+                    // diminui a prioridade de algum elemento que ainda está no heap
+                    let Some(&id) = handles
+                        .keys()
+                        .min_by_key(|&&id| id.wrapping_mul(random as usize))
+                    else {
+                        continue;
+                    };
+                    let (handle, old) = handles[&id];
+                    let new = old - old.min(priority);
+
+                    unsafe { heap.decrease_key(handle, (new, id)) };
+                    reference.remove(&(old, id));
+                    reference.insert((new, id));
+                    handles.insert(id, (handle, new));
+                }
+                _ => {
+                    // junta um heap novo, cujos handles passam a valer nesse
+                    let mut other = H::default();
+                    for extra in 0..random % 20 {
+                        reference.insert(push(
+                            &mut other,
+                            (priority + extra * 37) % 1000,
+                            &mut handles,
+                        ));
+                    }
+                    heap.meld(other);
+                }
+            }
+
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek(), reference.first());
+        }
+
+        let rest: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(rest, reference.into_iter().collect::<Vec<_>>());
+        assert!(heap.is_empty());
+    }
+
+    fn randomized<H: MergeableHeap<(u64, usize)>>() {
+        for seed in [1, 2, 3, 0xdead_beef] {
+            against_reference::<H>(seed);
+        }
+    }
+
+    fn heap_sort<H: MergeableHeap<u64>>() {
+        let values = pseudo_random(5000, 99);
+        let mut heap = H::default();
+        for &value in &values {
+            heap.push(value);
+        }
+
+        let mut expected = values;
+        expected.sort();
+        let sorted: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(sorted, expected);
+    }
+
+    fn meld_empty<H: MergeableHeap<u64>>() {
+        let mut heap = H::default();
+        heap.meld(H::default());
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+
+        let mut other = H::default();
+        let handle = other.push(5);
+        heap.meld(other);
+        heap.meld(H::default());
+        unsafe { heap.decrease_key(handle, 1) };
+        assert_eq!(heap.pop(), Some(1));
+    }
+
+    // conta quantas vezes os elementos foram dropados. só o número entra nas comparações.
+    struct Tracked(u64, Rc<Cell<usize>>);
+
+    impl PartialEq for Tracked {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Tracked {}
+
+    impl PartialOrd for Tracked {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Tracked {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    fn drops_everything<H: MergeableHeap<Tracked>>() {
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut heap = H::default();
+            let mut other = H::default();
+            for (i, value) in pseudo_random(1000, 5).into_iter().enumerate() {
+                match i % 2 {
+                    0 => heap.push(Tracked(value, drops.clone())),
+                    _ => other.push(Tracked(value, drops.clone())),
+                };
+            }
+            heap.meld(other);
+            for _ in 0..300 {
+                heap.pop();
+            }
+            assert_eq!(drops.get(), 300);
+        }
+
+        assert_eq!(drops.get(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "new element should be <= the current one")]
+    fn test_decrease_key_with_larger_element() {
+        let mut heap = PairingHeap::new();
+        let handle = heap.push(1);
+        unsafe { heap.decrease_key(handle, 2) };
+    }
+
+    macro_rules! suite {
+        ($($module:ident: $heap:ident),* $(,)?) => {
+            $(
+                mod $module {
+                    use super::*;
+
+                    #[test]
+                    fn test_against_reference() {
+                        randomized::<$heap<(u64, usize)>>();
+                    }
+
+                    #[test]
+                    fn test_heap_sort() {
+                        heap_sort::<$heap<u64>>();
+                    }
+
+                    #[test]
+                    fn test_meld_empty() {
+                        meld_empty::<$heap<u64>>();
+                    }
+
+                    #[test]
+                    fn test_drops_everything() {
+                        drops_everything::<$heap<Tracked>>();
+                    }
+                }
+            )*
+        };
+    }
+
+    suite! {
+        pairing: PairingHeap,
+        leftist: LeftistHeap,
+        skew: SkewHeap,
+        binomial: BinomialHeap,
+        fibonacci: FibonacciHeap,
+    }
+}
@@ -0,0 +1,487 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::fmt;
+
+/// Fila de prioridade de duas pontas: acesso ao menor e ao maior elemento ao mesmo tempo.
+///
+/// É uma árvore binária completa num [`Vector`], como o [`BinaryHeap`], mas os níveis se
+/// alternam: nos níveis pares (0, 2, 4, ...) cada node é menor ou igual a todos os
+/// descendentes, e nos ímpares, maior ou igual. Então o menor está na raiz e o maior é um dos
+/// dois filhos dela. Pra manter isso, um elemento sobe ou desce pulando de avô em avô, que
+/// está num nível do mesmo tipo.
+///
+/// [`BinaryHeap`]: crate::binary_heap::BinaryHeap
+pub(crate) struct MinMaxHeap<T> {
+    data: Vector<T>,
+}
+
+// nível de uma posição na árvore: 0 pra raiz, 1 pros filhos dela e assim por diante.
+fn is_min_level(index: usize) -> bool {
+    (index + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: Vector::new(),
+        }
+    }
+
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vector::with_capacity(capacity),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Menor elemento. O(1).
+    pub(crate) fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Maior elemento. O(1).
+    pub(crate) fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.data[index])
+    }
+
+    /// O(log n).
+    pub(crate) fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.bubble_up(self.data.len() - 1);
+    }
+
+    /// Remove e retorna o menor elemento. O(log n).
+    pub(crate) fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    /// Remove e retorna o maior elemento. O(log n).
+    pub(crate) fn pop_max(&mut self) -> Option<T> {
+        self.remove_at(self.max_index()?)
+    }
+
+    /// Insere `element` e remove o menor, que pode ser o próprio `element`. Mais rápido que um
+    /// `push` seguido de `pop_min`: se o novo elemento já for o menor, ele nem entra no heap.
+    /// O(log n).
+    pub(crate) fn push_pop_min(&mut self, mut element: T) -> T {
+        match self.data.first() {
+            Some(min) if *min < element => {
+                std::mem::swap(&mut self.data[0], &mut element);
+                self.trickle_down(0);
+                element
+            }
+            _ => element,
+        }
+    }
+
+    /// Insere `element` e remove o maior, que pode ser o próprio `element`. O(log n).
+    pub(crate) fn push_pop_max(&mut self, mut element: T) -> T {
+        let Some(index) = self.max_index() else {
+            return element;
+        };
+        if self.data[index] <= element {
+            return element;
+        }
+
+        // o novo elemento pode ser menor até que o mínimo, e aí é ele quem vira a raiz e o
+        // mínimo antigo que desce pro lugar do máximo.
+        std::mem::swap(&mut self.data[index], &mut element);
+        if index != 0 && self.data[index] < self.data[0] {
+            self.data.swap(0, index);
+        }
+        self.trickle_down(index);
+
+        element
+    }
+
+    /// Os elementos em ordem crescente. O(n log n).
+    pub(crate) fn into_vector_asc(mut self) -> Vector<T> {
+        let mut sorted = Vector::with_capacity(self.len());
+        while let Some(element) = self.pop_min() {
+            sorted.push(element);
+        }
+
+        sorted
+    }
+
+    /// Os elementos na ordem interna do heap, sem custo nenhum.
+    pub(crate) fn into_vector(self) -> Vector<T> {
+        self.data
+    }
+
+    /// Elementos em ordem arbitrária (a ordem interna do heap).
+    pub(crate) fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    // o maior é a raiz (se só tiver ela) ou o maior dos filhos da raiz.
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(match self.data[1] < self.data[2] {
+                true => 2,
+                false => 1,
+            }),
+        }
+    }
+
+    // o último elemento vai pro lugar do removido e desce. como o removido é a raiz ou um
+    // filho dela, o último nunca precisa subir.
+    fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.data.len() {
+            return None;
+        }
+
+        let element = self.data.swap_remove(index);
+        if index < self.data.len() {
+            self.trickle_down(index);
+        }
+
+        Some(element)
+    }
+
+    // `a` é "melhor" que `b` pro tipo de nível dado: menor num nível de mínimo e maior num
+    // nível de máximo.
+    fn better(&self, a: usize, b: usize, min: bool) -> bool {
+        match min {
+            true => self.data[a] < self.data[b],
+            false => self.data[a] > self.data[b],
+        }
+    }
+
+    // um elemento novo no fim primeiro se compara com o pai, que está no nível do outro tipo.
+    // se estiver do lado errado, troca com ele; depois sobe de avô em avô pelos níveis do
+    // tipo em que parou.
+    fn bubble_up(&mut self, mut index: usize) {
+        if index == 0 {
+            return;
+        }
+
+        let mut min = is_min_level(index);
+        let parent = (index - 1) / 2;
+        if self.better(parent, index, min) {
+            self.data.swap(index, parent);
+            index = parent;
+            min = !min;
+        }
+
+        while index >= 3 {
+            let grandparent = ((index - 1) / 2 - 1) / 2;
+            if !self.better(index, grandparent, min) {
+                break;
+            }
+
+            self.data.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    // desce o elemento trocando com o melhor entre filhos e netos. se foi pra um neto, ele
+    // pode ter ficado do lado errado do novo pai (que é do outro tipo), e aí troca com ele.
+    fn trickle_down(&mut self, mut index: usize) {
+        let min = is_min_level(index);
+        let length = self.data.len();
+
+        loop {
+            let first_child = 2 * index + 1;
+            if first_child >= length {
+                break;
+            }
+
+            let first_grandchild = 4 * index + 3;
+            let candidates = (first_child..first_child + 2)
+                .chain(first_grandchild..first_grandchild + 4)
+                .filter(|&candidate| candidate < length);
+            let best = candidates
+                .reduce(|best, candidate| match self.better(candidate, best, min) {
+                    true => candidate,
+                    false => best,
+                })
+                .expect("node has at least one child");
+
+            if !self.better(best, index, min) {
+                break;
+            }
+            self.data.swap(best, index);
+
+            if best < first_grandchild {
+                break;
+            }
+
+            let parent = (best - 1) / 2;
+            if self.better(parent, best, min) {
+                self.data.swap(best, parent);
+            }
+            index = best;
+        }
+    }
+}
+
+impl<T: Ord> From<Vector<T>> for MinMaxHeap<T> {
+    /// Reaproveita o vetor e reorganiza ele como heap em O(n), de baixo pra cima.
+    fn from(data: Vector<T>) -> Self {
+        let mut heap = Self { data };
+        for index in (0..heap.data.len() / 2).rev() {
+            heap.trickle_down(index);
+        }
+
+        heap
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinMaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vector<T>>())
+    }
+}
+
+impl<T: Ord> Extend<T> for MinMaxHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> Clone for MinMaxHeap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for MinMaxHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.data.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // xorshift simples, só pra gerar entradas reproduzíveis nos testes
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<u64> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed
+            })
+            .collect()
+    }
+
+    // cada elemento tem que estar do lado certo de todos os ancestrais, conforme o nível de
+    // cada um
+    fn assert_valid<T: Ord + fmt::Debug>(heap: &MinMaxHeap<T>) {
+        for index in 1..heap.data.len() {
+            let mut ancestor = index;
+            while ancestor > 0 {
+                ancestor = (ancestor - 1) / 2;
+                let (a, x) = (&heap.data[ancestor], &heap.data[index]);
+                match is_min_level(ancestor) {
+                    true => assert!(a <= x, "{a:?} > {x:?} in {heap:?}"),
+                    false => assert!(a >= x, "{a:?} < {x:?} in {heap:?}"),
+                }
+            }
+        }
+    }
+
+    // todas as sequências de tamanho `length` com valores em `0..base`
+    fn all_sequences(length: u32, base: u64) -> impl Iterator<Item = Vec<u64>> {
+        (0..base.pow(length)).map(move |mut code| {
+            (0..length)
+                .map(|_| {
+                    let digit = code % base;
+                    code /= base;
+                    digit
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_levels() {
+        let levels: Vec<bool> = (0..15).map(is_min_level).collect();
+        let expected = [[true].as_slice(), &[false; 2], &[true; 4], &[false; 8]].concat();
+        assert_eq!(levels, expected);
+    }
+
+    #[test]
+    fn test_peek_and_pop_both_ends() {
+        let mut heap = MinMaxHeap::new();
+        assert_eq!(heap.peek_min(), None);
+        assert_eq!(heap.peek_max(), None);
+        assert_eq!(heap.pop_max(), None);
+
+        heap.extend([5, 3, 8, 1, 9, 2, 7]);
+        assert_valid(&heap);
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&9));
+
+        assert_eq!(heap.pop_max(), Some(9));
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(8));
+        assert_eq!(heap.pop_min(), Some(2));
+        assert_valid(&heap);
+        assert_eq!(heap.into_vector_asc(), [3, 5, 7]);
+    }
+
+    #[test]
+    fn test_exhaustive_small_inputs() {
+        // toda sequência curta, montada por pushes e por heapify, tem que sair em ordem pelas
+        // duas pontas (e alternando entre elas)
+        for length in 0..=7 {
+            for values in all_sequences(length, 4) {
+                let mut sorted = values.clone();
+                sorted.sort();
+
+                let mut pushed = MinMaxHeap::new();
+                for &value in &values {
+                    pushed.push(value);
+                    assert_valid(&pushed);
+                }
+                let heapified: MinMaxHeap<u64> = values.iter().copied().collect();
+                assert_valid(&heapified);
+
+                assert_eq!(pushed.clone().into_vector_asc(), sorted[..]);
+
+                let mut descending = Vec::new();
+                let mut from_max = heapified.clone();
+                while let Some(max) = from_max.pop_max() {
+                    assert_valid(&from_max);
+                    descending.push(max);
+                }
+                assert!(descending.iter().rev().eq(&sorted));
+
+                // alternando: menor, maior, menor, ...
+                let mut alternating = heapified;
+                let (mut low, mut high) = (0, sorted.len());
+                for step in 0..sorted.len() {
+                    match step % 2 {
+                        0 => {
+                            assert_eq!(alternating.pop_min(), Some(sorted[low]));
+                            low += 1;
+                        }
+                        _ => {
+                            high -= 1;
+                            assert_eq!(alternating.pop_max(), Some(sorted[high]));
+                        }
+                    }
+                    assert_valid(&alternating);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_pop_exhaustive() {
+        for length in 0..=5 {
+            for values in all_sequences(length, 5) {
+                let heap: MinMaxHeap<u64> = values.iter().copied().collect();
+                for element in 0..5 {
+                    let mut reference = values.clone();
+                    reference.push(element);
+                    reference.sort();
+
+                    let mut min_heap = heap.clone();
+                    assert_eq!(min_heap.push_pop_min(element), reference[0]);
+                    assert_valid(&min_heap);
+                    assert_eq!(min_heap.into_vector_asc(), reference[1..]);
+
+                    let mut max_heap = heap.clone();
+                    assert_eq!(
+                        max_heap.push_pop_max(element),
+                        reference[reference.len() - 1]
+                    );
+                    assert_valid(&max_heap);
+                    assert_eq!(max_heap.into_vector_asc(), reference[..reference.len() - 1]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_operations_against_reference() {
+        let mut heap = MinMaxHeap::new();
+        let mut reference: Vec<u64> = Vec::new();
+
+        for random in pseudo_random(5000, 42) {
+            let value = random % 100;
+            match random % 6 {
+                0 | 1 => {
+                    heap.push(value);
+                    reference.push(value);
+                }
+                2 => {
+                    reference.sort();
+                    let expected = (!reference.is_empty()).then(|| reference.remove(0));
+                    assert_eq!(heap.pop_min(), expected);
+                }
+                3 => {
+                    reference.sort();
+                    assert_eq!(heap.pop_max(), reference.pop());
+                }
+                4 => {
+                    reference.push(value);
+                    reference.sort();
+                    assert_eq!(heap.push_pop_min(value), reference.remove(0));
+                }
+                _ => {
+                    reference.push(value);
+                    reference.sort();
+                    assert_eq!(heap.push_pop_max(value), reference.pop().unwrap());
+                }
+            }
+
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek_min(), reference.iter().min());
+            assert_eq!(heap.peek_max(), reference.iter().max());
+        }
+        assert_valid(&heap);
+    }
+
+    #[test]
+    fn test_bounded_top_k() {
+        // os k maiores de um fluxo: com o heap cheio, cada elemento novo entra e o menor sai
+        let values = pseudo_random(10_000, 8);
+        let k = 16;
+
+        let mut top = MinMaxHeap::with_capacity(k);
+        for &value in &values {
+            match top.len() < k {
+                true => top.push(value),
+                false => {
+                    top.push_pop_min(value);
+                }
+            }
+        }
+
+        let mut expected = values;
+        expected.sort();
+        assert_eq!(top.peek_max(), expected.last());
+        assert_eq!(top.into_vector_asc(), expected[expected.len() - k..]);
+    }
+}
@@ -0,0 +1,309 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{fmt, marker::PhantomData, ptr::NonNull};
+
+/// Pairing heap (min-heap): uma árvore qualquer em que todo pai é menor ou igual aos filhos.
+///
+/// Não tem nenhuma regra de forma, o que deixa quase tudo trivial: juntar dois heaps é só
+/// pendurar a raiz maior como filha da menor, O(1). Toda a arrumação fica pro `pop`, que junta
+/// os filhos da raiz dois a dois (daí o nome) e tem custo amortizado O(log n).
+pub(crate) struct PairingHeap<T> {
+    root: Link<T>,
+    length: usize,
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// os filhos de um node formam uma lista simplesmente encadeada por `sibling`, começando no
+// `child` do pai. o `prev` aponta pro irmão anterior, ou pro pai se o node for o primeiro
+// filho: é o que permite cortar um node do meio da árvore em O(1) no `decrease_key`.
+struct Node<T> {
+    element: T,
+    child: Link<T>,
+    sibling: Link<T>,
+    prev: Link<T>,
+}
+
+type Link<T> = Option<NonNull<Node<T>>>;
+
+/// Referência pra um elemento que está no heap, devolvida pelo `push` e usada no
+/// `decrease_key`.
+pub(crate) struct Handle<T> {
+    node: NonNull<Node<T>>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+unsafe impl<T: Send> Send for PairingHeap<T> {}
+unsafe impl<T: Sync> Sync for PairingHeap<T> {}
+
+impl<T: Ord> PairingHeap<T> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            root: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// O(1).
+    pub(crate) fn push(&mut self, element: T) -> Handle<T> {
+        let node = NonNull::from(Box::leak(Box::new(Node {
+            element,
+            child: None,
+            sibling: None,
+            prev: None,
+        })));
+
+        self.root = Some(match self.root {
+            Some(root) => unsafe { Self::link(root, node) },
+            None => node,
+        });
+        self.length += 1;
+
+        Handle { node }
+    }
+
+    /// Menor elemento, sem remover. O(1).
+    pub(crate) fn peek(&self) -> Option<&T> {
+        self.root.map(|root| unsafe { &(*root.as_ptr()).element })
+    }
+
+    /// Remove e retorna o menor elemento. O(log n) amortizado.
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+
+        unsafe {
+            self.root = Self::combine_siblings((*root.as_ptr()).child);
+            self.length -= 1;
+
+            Some(Box::from_raw(root.as_ptr()).element)
+        }
+    }
+
+    /// Move todos os elementos de `other` pra esse heap. O(1). Os handles de `other` continuam
+    /// valendo, agora nesse heap.
+    pub(crate) fn meld(&mut self, mut other: Self) {
+        let Some(other_root) = other.root.take() else {
+            return;
+        };
+
+        self.root = Some(match self.root {
+            Some(root) => unsafe { Self::link(root, other_root) },
+            None => other_root,
+        });
+        self.length += std::mem::take(&mut other.length);
+    }
+
+    /// Troca o elemento de `handle` por um menor ou igual. O(1), mas o próximo `pop` paga a
+    /// conta.
+    ///
+    /// # Safety
+    ///
+    /// `handle` precisa ter vindo de um `push` nesse heap (ou num heap que foi juntado a ele
+    /// com `meld`), e o elemento ainda não pode ter sido removido.
+    ///
+    /// # Panics
+    ///
+    /// Se o novo elemento for maior que o atual.
+    pub(crate) unsafe fn decrease_key(&mut self, handle: Handle<T>, element: T) {
+        let node = handle.node;
+
+        unsafe {
+            assert!(
+                element <= (*node.as_ptr()).element,
+                "new element should be <= the current one"
+            );
+            (*node.as_ptr()).element = element;
+
+            // se não for a raiz, o node sai (junto com a subárvore dele) da lista de filhos do
+            // pai e é juntado de novo à raiz. a subárvore continua em ordem, já que o node só
+            // diminuiu.
+            let Some(prev) = (*node.as_ptr()).prev else {
+                return;
+            };
+
+            let next = (*node.as_ptr()).sibling.take();
+            match (*prev.as_ptr()).child == Some(node) {
+                true => (*prev.as_ptr()).child = next,
+                false => (*prev.as_ptr()).sibling = next,
+            }
+            if let Some(next) = next {
+                (*next.as_ptr()).prev = Some(prev);
+            }
+            (*node.as_ptr()).prev = None;
+
+            let root = self.root.expect("heap with a handle should not be empty");
+            self.root = Some(Self::link(root, node));
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        while self.pop().is_some() {}
+    }
+
+    // junta duas raízes (sem pai nem irmãos): a maior vira o primeiro filho da menor.
+    unsafe fn link(a: NonNull<Node<T>>, b: NonNull<Node<T>>) -> NonNull<Node<T>> {
+        unsafe {
+            let (parent, child) = match (*b.as_ptr()).element < (*a.as_ptr()).element {
+                true => (b, a),
+                false => (a, b),
+            };
+
+            (*child.as_ptr()).sibling = (*parent.as_ptr()).child;
+            if let Some(first) = (*parent.as_ptr()).child {
+                (*first.as_ptr()).prev = Some(child);
+            }
+            (*child.as_ptr()).prev = Some(parent);
+            (*parent.as_ptr()).child = Some(child);
+
+            parent
+        }
+    }
+
+    // junta a lista de irmãos que começa em `first` numa árvore só, em duas passadas: primeiro
+    // da esquerda pra direita, de dois em dois, e depois os pares resultantes da direita pra
+    // esquerda. juntar tudo numa passada só funcionaria, mas aí o custo amortizado vira O(n).
+    unsafe fn combine_siblings(first: Link<T>) -> Link<T> {
+        unsafe {
+            // os pares vão sendo empilhados usando o próprio `sibling` como encadeamento, então
+            // no fim a pilha já está na ordem da segunda passada.
+            let mut pairs: Link<T> = None;
+            let mut current = first;
+
+            while let Some(a) = current {
+                let merged = match (*a.as_ptr()).sibling {
+                    Some(b) => {
+                        current = (*b.as_ptr()).sibling;
+                        (*a.as_ptr()).sibling = None;
+                        (*b.as_ptr()).sibling = None;
+                        Self::link(a, b)
+                    }
+                    None => {
+                        current = None;
+                        a
+                    }
+                };
+
+                (*merged.as_ptr()).sibling = pairs;
+                pairs = Some(merged);
+            }
+
+            let mut root = pairs?;
+            let mut rest = (*root.as_ptr()).sibling.take();
+            while let Some(pair) = rest {
+                rest = (*pair.as_ptr()).sibling.take();
+                root = Self::link(root, pair);
+            }
+            (*root.as_ptr()).prev = None;
+
+            Some(root)
+        }
+    }
+}
+
+// sem `T: Ord` aqui o `Drop` não conseguiria chamar o `pop`, então os nodes são liberados
+// percorrendo a árvore com uma pilha explícita, sem recursão (a árvore pode ter altura n).
+impl<T> Drop for PairingHeap<T> {
+    fn drop(&mut self) {
+        let mut pending: Vector<NonNull<Node<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            pending.extend(node.child);
+            pending.extend(node.sibling);
+        }
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PairingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for PairingHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PairingHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PairingHeap")
+            .field("len", &self.length)
+            .field(
+                "min",
+                &self.root.map(|root| unsafe { &(*root.as_ptr()).element }),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop() {
+        let mut heap = PairingHeap::new();
+        assert_eq!(heap.pop(), None);
+
+        heap.extend([5, 3, 8, 1, 9, 2]);
+        assert_eq!(heap.len(), 6);
+        assert_eq!(heap.peek(), Some(&1));
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [1, 2, 3, 5, 8, 9]);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key_cuts_subtree() {
+        let mut heap = PairingHeap::new();
+        heap.push(1);
+        let handles: Vec<_> = (10..20).map(|x| heap.push(x)).collect();
+
+        // o pop reorganiza tudo, então os handles passam a apontar pra nodes no meio da árvore
+        assert_eq!(heap.pop(), Some(1));
+        unsafe {
+            heap.decrease_key(handles[7], 0);
+            heap.decrease_key(handles[3], 12);
+        }
+
+        let popped: Vec<_> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, [0, 10, 11, 12, 12, 14, 15, 16, 18, 19]);
+    }
+
+    #[test]
+    fn test_deep_tree_drop() {
+        // inserir em ordem decrescente faz cada raiz nova ficar por cima da anterior, formando
+        // uma corrente com altura n: o drop não pode ser recursivo.
+        let heap: PairingHeap<u32> = (0..100_000).rev().collect();
+        assert_eq!(heap.peek(), Some(&0));
+    }
+}
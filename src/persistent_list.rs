@@ -0,0 +1,286 @@
+#![allow(unused)]
+
+use std::{fmt, rc::Rc, sync::Arc};
+
+// existem duas versões da lista: `PersistentList`, que conta referências com `Rc`, e
+// `ArcPersistentList`, com `Arc`, que pode ser compartilhada entre threads. só muda o ponteiro,
+// então geramos cada uma num módulo próprio a partir do mesmo corpo.
+macro_rules! persistent_list {
+    ($module:ident, $pointer:ident) => {
+        pub(crate) mod $module {
+            use super::*;
+
+            /// Lista imutável e persistente, no estilo das listas de OCaml e Haskell.
+            ///
+            /// Nenhuma operação modifica a lista: o `prepend` devolve uma lista nova cuja cauda
+            /// é a lista antiga, compartilhada, sem copiar nada. Várias listas podem dividir o
+            /// mesmo final, e cada node vive enquanto alguma delas ainda aponta pra ele.
+            pub(crate) struct PersistentList<T> {
+                head: Link<T>,
+                length: usize,
+            }
+
+            struct Node<T> {
+                element: T,
+                next: Link<T>,
+            }
+
+            // cada node pode ter vários "donos" (todas as listas que passam por ele),
+            // então usamos um ponteiro com contagem de referências no lugar do `Box`.
+            type Link<T> = Option<$pointer<Node<T>>>;
+
+            impl<T> PersistentList<T> {
+                pub(crate) const fn new() -> Self {
+                    Self {
+                        head: None,
+                        length: 0,
+                    }
+                }
+
+                pub(crate) const fn len(&self) -> usize {
+                    self.length
+                }
+
+                pub(crate) const fn is_empty(&self) -> bool {
+                    self.head.is_none()
+                }
+
+                /// Lista nova com `element` na frente e `self` como cauda. O(1).
+                pub(crate) fn prepend(&self, element: T) -> Self {
+                    Self {
+                        head: Some($pointer::new(Node {
+                            element,
+                            next: self.head.clone(),
+                        })),
+                        length: self.length + 1,
+                    }
+                }
+
+                pub(crate) fn head(&self) -> Option<&T> {
+                    self.head.as_ref().map(|node| &node.element)
+                }
+
+                /// Tudo menos o primeiro elemento, compartilhando os nodes. A cauda da lista
+                /// vazia é a própria lista vazia.
+                pub(crate) fn tail(&self) -> Self {
+                    match &self.head {
+                        Some(node) => Self {
+                            head: node.next.clone(),
+                            length: self.length - 1,
+                        },
+                        None => Self::new(),
+                    }
+                }
+
+                pub(crate) fn iter(&self) -> Iter<'_, T> {
+                    Iter {
+                        next: self.head.as_deref(),
+                        remaining: self.length,
+                    }
+                }
+            }
+
+            // clonar só incrementa o contador do primeiro node, a lista não é copiada.
+            impl<T> Clone for PersistentList<T> {
+                fn clone(&self) -> Self {
+                    Self {
+                        head: self.head.clone(),
+                        length: self.length,
+                    }
+                }
+            }
+
+            impl<T> Drop for PersistentList<T> {
+                fn drop(&mut self) {
+                    // igual à `LinkedList`, o drop padrão seria recursivo. aqui ainda tem um
+                    // detalhe: só podemos continuar descendo enquanto formos o último dono do
+                    // node. quando encontramos um node compartilhado, a outra lista continua
+                    // usando o resto, então paramos ali.
+                    let mut head = self.head.take();
+                    while let Some(node) = head {
+                        // `into_inner` só devolve o node se essa era a última referência.
+                        // no `Arc`, diferente do `try_unwrap`, isso não tem condição de
+                        // corrida: se duas threads soltam o mesmo node ao mesmo tempo,
+                        // exatamente uma delas recebe ele.
+                        head = $pointer::into_inner(node).and_then(|mut node| node.next.take());
+                    }
+                }
+            }
+
+            impl<T> Default for PersistentList<T> {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl<T: fmt::Debug> fmt::Debug for PersistentList<T> {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.debug_list().entries(self.iter()).finish()
+                }
+            }
+
+            impl<T: PartialEq> PartialEq for PersistentList<T> {
+                fn eq(&self, other: &Self) -> bool {
+                    self.length == other.length && self.iter().eq(other.iter())
+                }
+            }
+
+            impl<T: Eq> Eq for PersistentList<T> {}
+
+            impl<T> FromIterator<T> for PersistentList<T> {
+                fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+                    // só conseguimos adicionar na frente, então juntamos tudo antes e
+                    // montamos a lista de trás pra frente pra manter a ordem do iterador.
+                    let mut elements: crate::vector::Vector<T> = iter.into_iter().collect();
+                    let mut list = Self::new();
+                    while let Some(element) = elements.pop() {
+                        list = list.prepend(element);
+                    }
+
+                    list
+                }
+            }
+
+            impl<'a, T> IntoIterator for &'a PersistentList<T> {
+                type Item = &'a T;
+                type IntoIter = Iter<'a, T>;
+
+                fn into_iter(self) -> Iter<'a, T> {
+                    self.iter()
+                }
+            }
+
+            /// Iterador que empresta os elementos da lista, da cabeça até o final.
+            pub(crate) struct Iter<'a, T> {
+                next: Option<&'a Node<T>>,
+                remaining: usize,
+            }
+
+            impl<'a, T> Iterator for Iter<'a, T> {
+                type Item = &'a T;
+
+                fn next(&mut self) -> Option<&'a T> {
+                    self.next.map(|node| {
+                        self.next = node.next.as_deref();
+                        self.remaining -= 1;
+                        &node.element
+                    })
+                }
+
+                fn size_hint(&self) -> (usize, Option<usize>) {
+                    (self.remaining, Some(self.remaining))
+                }
+            }
+
+            impl<T> ExactSizeIterator for Iter<'_, T> {}
+        }
+    };
+}
+
+persistent_list!(rc, Rc);
+persistent_list!(sync, Arc);
+
+pub(crate) use rc::PersistentList;
+pub(crate) use sync::PersistentList as ArcPersistentList;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prepend_head_tail() {
+        let empty = PersistentList::new();
+        assert!(empty.is_empty());
+        assert_eq!(empty.head(), None);
+        assert!(empty.tail().is_empty());
+
+        let one = empty.prepend(1);
+        let two = one.prepend(2);
+        let three = two.prepend(3);
+
+        // nenhuma das listas antigas foi modificada
+        assert_eq!(empty.len(), 0);
+        assert_eq!(one.iter().copied().collect::<Vec<_>>(), [1]);
+        assert_eq!(two.iter().copied().collect::<Vec<_>>(), [2, 1]);
+        assert_eq!(three.iter().copied().collect::<Vec<_>>(), [3, 2, 1]);
+
+        assert_eq!(three.head(), Some(&3));
+        assert_eq!(three.tail(), two);
+        assert_eq!(three.tail().tail().len(), 1);
+        assert_eq!(three.iter().len(), 3);
+    }
+
+    #[test]
+    fn test_shared_tail() {
+        let shared: PersistentList<String> = ["c", "d"].into_iter().map(String::from).collect();
+        let left = shared.prepend(String::from("a"));
+        let right = shared.prepend(String::from("b"));
+
+        assert_eq!(format!("{left:?}"), r#"["a", "c", "d"]"#);
+        assert_eq!(format!("{right:?}"), r#"["b", "c", "d"]"#);
+
+        // as caudas são o mesmo node, não uma cópia
+        let left_tail = left.tail();
+        let right_tail = right.tail();
+        assert!(std::ptr::eq(
+            left_tail.head().unwrap(),
+            right_tail.head().unwrap()
+        ));
+
+        // soltar uma das listas não afeta a outra
+        drop(left);
+        drop(shared);
+        assert_eq!(right.iter().count(), 3);
+        assert_eq!(right_tail.head().map(String::as_str), Some("c"));
+    }
+
+    #[test]
+    fn test_drop_is_iterative_and_stops_at_shared_nodes() {
+        let tracker = Rc::new(());
+        let base: PersistentList<Rc<()>> = (0..3).map(|_| Rc::clone(&tracker)).collect();
+        let extended = base
+            .prepend(Rc::clone(&tracker))
+            .prepend(Rc::clone(&tracker));
+        assert_eq!(Rc::strong_count(&tracker), 6);
+
+        // os dois nodes exclusivos somem, os três compartilhados continuam vivos em `base`
+        drop(extended);
+        assert_eq!(Rc::strong_count(&tracker), 4);
+        drop(base);
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        // uma corrente grande o suficiente pra estourar a stack se o drop fosse recursivo
+        let mut long = PersistentList::new();
+        for i in 0..1_000_000 {
+            long = long.prepend(i);
+        }
+        let tail = long.tail();
+        drop(long);
+        assert_eq!(tail.head(), Some(&999_998));
+    }
+
+    #[test]
+    fn test_arc_list_across_threads() {
+        let shared: ArcPersistentList<i32> = (1..=100).collect();
+
+        let sums: Vec<i32> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let mine = shared.prepend(i);
+                    scope.spawn(move || mine.iter().sum::<i32>())
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        assert_eq!(sums, [5050, 5051, 5052, 5053]);
+        assert_eq!(shared.len(), 100);
+
+        let mut long = ArcPersistentList::new();
+        for i in 0..1_000_000 {
+            long = long.prepend(i);
+        }
+        drop(long);
+    }
+}
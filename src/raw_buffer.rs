@@ -0,0 +1,353 @@
+#![allow(unused)]
+
+use crate::allocator::{Global, RawAlloc};
+use std::{alloc::Layout, fmt, ptr::NonNull};
+
+/// Bloco de memória na HEAP com espaço pra `capacity` elementos do tipo `T`.
+///
+/// Só cuida da memória: calcular layouts, alocar, crescer e liberar. Ele não sabe quais
+/// posições estão inicializadas, isso é responsabilidade de quem usa (`Vector`, `Deque`, ...).
+/// Por isso o `Drop` dele só libera o bloco e nunca dropa elemento nenhum.
+pub(crate) struct RawBuffer<T, A: RawAlloc = Global> {
+    // ponteiro que aponta para o conteúdo da célula.
+    // em rust, usamos `NonNull` para indicar pro compilador que esse ponteiro
+    // nunca deve ser nulo.
+    // isso também abre portas para mais otimizações por parte do compilador.
+    ptr: NonNull<T>,
+    capacity: usize,
+    alloc: A,
+    growth: GrowthPolicy,
+}
+
+/// Estratégia usada pra escolher a nova capacidade quando o buffer enche.
+///
+/// Crescer mais de uma vez por vez gasta mais memória, mas diminui a quantidade de reallocs
+/// (que copiam o buffer inteiro). Qualquer crescimento geométrico mantém o `push` em O(1)
+/// amortizado; o incremento fixo não, mas desperdiça no máximo `n` elementos.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GrowthPolicy {
+    /// dobra a capacidade (1, 2, 4, 8, ...).
+    #[default]
+    Doubling,
+    /// cresce 50% por vez. desperdiça menos memória e, diferente do 2x, permite que blocos
+    /// liberados antes sejam reaproveitados pelo alocador.
+    OneAndHalf,
+    /// soma sempre a mesma quantidade de elementos.
+    Increment(usize),
+    /// igual ao `Doubling`, mas a primeira alocação já começa com uma capacidade mínima que
+    /// depende do tamanho do elemento (8 pra bytes, 4 até 1KiB, 1 acima disso), como a `std` faz.
+    /// evita vários reallocs minúsculos no começo.
+    SizeAware,
+}
+
+impl GrowthPolicy {
+    /// Nova capacidade a partir da `current`, garantindo pelo menos `required` elementos.
+    fn next_capacity(self, current: usize, required: usize, element_size: usize) -> usize {
+        // as contas não dão overflow: `current` nunca passa de `isize::MAX` (a não ser pra ZSTs,
+        // que não chegam aqui), então dobrar ainda cabe em `usize`.
+        let candidate = match self {
+            Self::Doubling => current * 2,
+            Self::OneAndHalf => current + current / 2,
+            Self::Increment(step) => current.saturating_add(step),
+            Self::SizeAware => {
+                let minimum = match element_size {
+                    1 => 8,
+                    size if size <= 1024 => 4,
+                    _ => 1,
+                };
+
+                (current * 2).max(minimum)
+            }
+        };
+
+        candidate.max(required)
+    }
+}
+
+/// Erro retornado pelas versões falíveis (`try_*`) das operações que alocam memória.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// a capacidade pedida não cabe em `usize` ou passa de `isize::MAX` bytes.
+    CapacityOverflow,
+    /// o alocador não conseguiu entregar a memória pedida.
+    AllocError { layout: Layout },
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => f.write_str("capacity overflow"),
+            Self::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+// as versões que não retornam `Result` são só uma camada em cima das falíveis.
+pub(crate) fn handle_reserve<R>(result: Result<R, TryReserveError>) -> R {
+    match result {
+        Ok(value) => value,
+        Err(TryReserveError::CapacityOverflow) => panic!("Capacity overflow"),
+        Err(TryReserveError::AllocError { .. }) => panic!("Memory allocation failed"),
+    }
+}
+
+// rust é paranoico com threads. ponteiros (*mut T) não implementam send/sync automaticamente
+// porque o compilador não sabe se é seguro. estamos basicamente dizendo "confia no pai".
+unsafe impl<T: Send, A: RawAlloc + Send> Send for RawBuffer<T, A> {}
+unsafe impl<T: Sync, A: RawAlloc + Sync> Sync for RawBuffer<T, A> {}
+
+impl<T> RawBuffer<T> {
+    pub(crate) fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<T, A: RawAlloc> RawBuffer<T, A> {
+    // tipos de tamanho zero (ZST), como `()` ou `struct Marker;`, não ocupam memória nenhuma.
+    // então nunca precisamos alocar nada pra eles: qualquer quantidade "cabe" no ponteiro dangling.
+    pub(crate) const IS_ZST: bool = std::mem::size_of::<T>() == 0;
+
+    pub(crate) fn new_in(alloc: A) -> Self {
+        Self {
+            // `dangling` cria um ponteiro não-nulo invalido mas alinhado.
+            // o que é seguro, a não ser que a gente o deferencie.
+            // pra ZSTs ler/escrever nesse ponteiro é válido, já que são zero bytes.
+            ptr: NonNull::dangling(),
+            // pra ZSTs fingimos que a capacidade é infinita, assim nunca precisamos crescer.
+            capacity: if Self::IS_ZST { usize::MAX } else { 0 },
+            alloc,
+            growth: GrowthPolicy::default(),
+        }
+    }
+
+    pub(crate) fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let mut buffer = Self::new_in(alloc);
+        buffer.try_reserve_exact(0, capacity)?;
+
+        Ok(buffer)
+    }
+
+    /// Ponteiro pro começo do bloco. Só as posições que o dono inicializou podem ser lidas.
+    pub(crate) const fn ptr(&self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+
+    pub(crate) const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) const fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    pub(crate) const fn growth_policy(&self) -> GrowthPolicy {
+        self.growth
+    }
+
+    pub(crate) fn set_growth_policy(&mut self, growth: GrowthPolicy) {
+        self.growth = growth;
+    }
+
+    /// Garante espaço pra `length + additional` elementos, crescendo de acordo com a
+    /// [`GrowthPolicy`] para manter o custo amortizado baixo.
+    pub(crate) fn try_reserve(
+        &mut self,
+        length: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let required = Self::required_capacity(length, additional)?;
+
+        if required <= self.capacity {
+            return Ok(());
+        }
+
+        let new_capacity =
+            self.growth
+                .next_capacity(self.capacity, required, std::mem::size_of::<T>());
+        self.try_resize_to(new_capacity)
+    }
+
+    /// Igual ao `try_reserve`, mas aloca exatamente o necessário, ignorando a [`GrowthPolicy`].
+    pub(crate) fn try_reserve_exact(
+        &mut self,
+        length: usize,
+        additional: usize,
+    ) -> Result<(), TryReserveError> {
+        let required = Self::required_capacity(length, additional)?;
+
+        if required <= self.capacity {
+            return Ok(());
+        }
+
+        self.try_resize_to(required)
+    }
+
+    fn required_capacity(length: usize, additional: usize) -> Result<usize, TryReserveError> {
+        // `checked_add` devolve `None` se a soma passar de `usize::MAX`.
+        // isso também cobre os ZSTs, que já estão com a capacidade "infinita".
+        length
+            .checked_add(additional)
+            .ok_or(TryReserveError::CapacityOverflow)
+    }
+
+    /// Diminui a capacidade pra `new_capacity`. Se ela já for menor ou igual, não faz nada.
+    /// Quem chama garante que nenhum elemento vivo fica depois de `new_capacity`.
+    pub(crate) fn try_shrink_to(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        if Self::IS_ZST || new_capacity >= self.capacity {
+            return Ok(());
+        }
+
+        self.try_resize_to(new_capacity)
+    }
+
+    /// Realoca o bloco pra exatamente `new_capacity` elementos.
+    /// O conteúdo é preservado até o menor dos dois tamanhos, inclusive posições
+    /// que o dono ainda considera "não inicializadas".
+    fn try_resize_to(&mut self, new_capacity: usize) -> Result<(), TryReserveError> {
+        // se um ZST chegou aqui é porque já temos `usize::MAX` elementos.
+        // não tem como contar mais que isso.
+        if Self::IS_ZST {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+
+        if new_capacity == 0 {
+            // encolher pra zero é só liberar tudo e voltar pro estado do `new`.
+            self.free();
+            self.ptr = NonNull::dangling();
+            self.capacity = 0;
+            return Ok(());
+        }
+
+        // malloc / realloc em rust exigem alinhamento explícito.
+        // o layout guarda o size + alignment. se errarmos o alinhamento, é undefined behaviour
+        // (terra do diabo). considere parecido com posix_memalign em vez do malloc.
+        // `Layout::array` já falha se o tamanho total passar de `isize::MAX`.
+        let new_layout =
+            Layout::array::<T>(new_capacity).map_err(|_| TryReserveError::CapacityOverflow)?;
+
+        let new_ptr = match self.capacity == 0 {
+            true => self.alloc.allocate(new_layout),
+            false => {
+                // se o layout atual foi criado com sucesso antes, não tem como falhar agora.
+                let old_layout = Layout::array::<T>(self.capacity).unwrap();
+                let old_ptr = self.ptr.cast::<u8>();
+                unsafe { self.alloc.reallocate(old_ptr, old_layout, new_layout) }
+            }
+        };
+
+        // se o realloc falha o bloco antigo continua válido, então só atualizamos
+        // o buffer quando temos certeza que a memória nova existe.
+        self.ptr = new_ptr
+            .ok_or(TryReserveError::AllocError { layout: new_layout })?
+            .cast();
+        self.capacity = new_capacity;
+
+        Ok(())
+    }
+
+    fn free(&mut self) {
+        if !Self::IS_ZST && self.capacity != 0 {
+            let layout = Layout::array::<T>(self.capacity).unwrap();
+            unsafe { self.alloc.deallocate(self.ptr.cast(), layout) }
+        }
+    }
+}
+
+impl<T, A: RawAlloc> Drop for RawBuffer<T, A> {
+    fn drop(&mut self) {
+        self.free()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocator::Counting;
+
+    #[test]
+    fn test_allocation_counts() {
+        let counting = Counting::new(Global);
+
+        {
+            let mut buffer: RawBuffer<u64, _> = RawBuffer::new_in(&counting);
+            // criar um buffer vazio nunca aloca
+            assert_eq!(counting.allocations(), 0);
+            assert_eq!(buffer.capacity(), 0);
+
+            buffer.try_reserve(0, 1).unwrap();
+            assert_eq!(counting.allocations(), 1);
+            assert_eq!(buffer.capacity(), 1);
+
+            // 1 -> 2 -> 4 -> 8: a primeira vez aloca, o resto realoca
+            for length in 1..8 {
+                buffer.try_reserve(length, 1).unwrap();
+            }
+            assert_eq!(buffer.capacity(), 8);
+            assert_eq!(counting.allocations(), 1);
+            assert_eq!(counting.reallocations(), 3);
+
+            // já cabe, nada acontece
+            buffer.try_reserve(4, 4).unwrap();
+            assert_eq!(counting.reallocations(), 3);
+        }
+
+        assert_eq!(counting.deallocations(), 1);
+        assert_eq!(counting.live(), 0);
+    }
+
+    #[test]
+    fn test_shrink_frees() {
+        let counting = Counting::new(Global);
+        let mut buffer: RawBuffer<u32, _> = RawBuffer::try_with_capacity_in(16, &counting).unwrap();
+        assert_eq!(buffer.capacity(), 16);
+
+        buffer.try_shrink_to(4).unwrap();
+        assert_eq!(buffer.capacity(), 4);
+        assert_eq!(counting.reallocations(), 1);
+
+        // encolher pra zero libera o bloco, e o drop depois não libera de novo
+        buffer.try_shrink_to(0).unwrap();
+        assert_eq!(counting.live(), 0);
+        drop(buffer);
+        assert_eq!(counting.deallocations(), 1);
+    }
+
+    #[test]
+    fn test_preserves_contents() {
+        let mut buffer: RawBuffer<u32> = RawBuffer::new();
+        buffer.try_reserve_exact(0, 2).unwrap();
+
+        unsafe {
+            buffer.ptr().write(1);
+            buffer.ptr().add(1).write(2);
+        }
+
+        buffer.try_reserve(2, 100).unwrap();
+        assert!(buffer.capacity() >= 102);
+        unsafe {
+            assert_eq!(buffer.ptr().read(), 1);
+            assert_eq!(buffer.ptr().add(1).read(), 2);
+        }
+    }
+
+    #[test]
+    fn test_zero_sized_never_allocates() {
+        let counting = Counting::new(Global);
+        let mut buffer: RawBuffer<(), _> = RawBuffer::new_in(&counting);
+
+        assert_eq!(buffer.capacity(), usize::MAX);
+        assert_eq!(buffer.try_reserve(1000, 1000), Ok(()));
+        assert_eq!(
+            buffer.try_reserve(usize::MAX, 1),
+            Err(TryReserveError::CapacityOverflow)
+        );
+
+        drop(buffer);
+        assert_eq!(counting.allocations(), 0);
+        assert_eq!(counting.deallocations(), 0);
+    }
+}
@@ -0,0 +1,388 @@
+#![allow(unused)]
+
+use crate::{
+    allocator::{Global, RawAlloc},
+    vector::Vector,
+};
+use std::{
+    alloc::Layout,
+    cell::{Cell, UnsafeCell},
+    ops::{Index, IndexMut},
+    ptr::{self, NonNull},
+};
+
+/// Vetor segmentado: os elementos ficam em blocos (chunks) que nunca são realocados.
+///
+/// O `Vector` usa realloc quando enche, o que pode mover todos os elementos de lugar.
+/// Aqui, quando um chunk enche, alocamos outro com o dobro do tamanho e deixamos os antigos
+/// onde estão. Isso garante que o endereço de um elemento nunca muda enquanto ele existir,
+/// por isso o `push` recebe só `&self` e devolve uma referência que continua válida
+/// mesmo depois de novos `push`.
+///
+/// O chunk `k` tem `FIRST_CHUNK << k` elementos, então o índice de qualquer elemento
+/// vira (chunk, offset) com algumas operações de bits, em O(1).
+pub(crate) struct SegmentedVector<T> {
+    // ponteiros pro começo de cada chunk. essa tabela pode até ser realocada, mas os chunks não.
+    // `UnsafeCell` porque o `push` recebe `&self` e precisa mexer aqui.
+    chunks: UnsafeCell<Vector<NonNull<T>>>,
+    length: Cell<usize>,
+}
+
+// mesma justificativa do `Vector`. não implementamos `Sync`: o `push` com `&self` não é
+// thread-safe (e o `Cell`/`UnsafeCell` já impedem isso automaticamente).
+unsafe impl<T: Send> Send for SegmentedVector<T> {}
+
+impl<T> SegmentedVector<T> {
+    const FIRST_CHUNK: usize = 8;
+    const IS_ZST: bool = std::mem::size_of::<T>() == 0;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            chunks: UnsafeCell::new(Vector::new()),
+            length: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.length.get()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Quantos elementos cabem nos chunks já alocados.
+    pub(crate) fn capacity(&self) -> usize {
+        Self::chunk_start(self.chunk_count())
+    }
+
+    // quantidade de elementos em todos os chunks antes do chunk `chunk`:
+    // 8 + 16 + ... + (8 << (chunk - 1)) = 8 * (2^chunk - 1)
+    const fn chunk_start(chunk: usize) -> usize {
+        Self::FIRST_CHUNK * ((1 << chunk) - 1)
+    }
+
+    const fn chunk_capacity(chunk: usize) -> usize {
+        Self::FIRST_CHUNK << chunk
+    }
+
+    // converte um índice global em (chunk, posição dentro do chunk).
+    // `index / 8 + 1` fica entre 2^k e 2^(k + 1) pros índices do chunk k, então o
+    // número do chunk é só a posição do bit mais significativo.
+    const fn locate(index: usize) -> (usize, usize) {
+        let chunk = (index / Self::FIRST_CHUNK + 1).ilog2() as usize;
+        (chunk, index - Self::chunk_start(chunk))
+    }
+
+    fn chunk_count(&self) -> usize {
+        unsafe { (*self.chunks.get()).len() }
+    }
+
+    fn chunk_ptr(&self, chunk: usize) -> NonNull<T> {
+        unsafe { (&*self.chunks.get())[chunk] }
+    }
+
+    fn slot(&self, index: usize) -> *mut T {
+        let (chunk, offset) = Self::locate(index);
+        unsafe { self.chunk_ptr(chunk).as_ptr().add(offset) }
+    }
+
+    /// Adiciona um elemento no final e devolve uma referência pra ele.
+    ///
+    /// A referência continua válida enquanto o vetor existir (e ninguém chamar `pop`,
+    /// que exige `&mut self` e por isso não pode acontecer enquanto ela estiver viva).
+    pub(crate) fn push(&self, element: T) -> &T {
+        let index = self.len();
+        let (chunk, offset) = Self::locate(index);
+
+        if chunk == self.chunk_count() {
+            let new_chunk = match Self::IS_ZST {
+                true => NonNull::dangling(),
+                false => {
+                    let layout =
+                        Layout::array::<T>(Self::chunk_capacity(chunk)).expect("Capacity overflow");
+                    Global
+                        .allocate(layout)
+                        .expect("Memory allocation failed")
+                        .cast()
+                }
+            };
+
+            // nenhuma referência pra tabela de chunks sobrevive fora desses métodos,
+            // então é seguro pegar um `&mut` aqui rapidinho.
+            unsafe { (*self.chunks.get()).push(new_chunk) };
+        }
+
+        unsafe {
+            let slot = self.chunk_ptr(chunk).as_ptr().add(offset);
+            // a posição ainda não foi entregue pra ninguém, então escrever nela não
+            // invalida nenhuma referência existente.
+            ptr::write(slot, element);
+            self.length.set(index + 1);
+
+            &*slot
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let length = self.len();
+        if length == 0 {
+            return None;
+        }
+
+        self.length.set(length - 1);
+        // os chunks continuam alocados pra serem reaproveitados nos próximos `push`.
+        Some(unsafe { ptr::read(self.slot(length - 1)) })
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        (index < self.len()).then(|| unsafe { &*self.slot(index) })
+    }
+
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        (index < self.len()).then(|| unsafe { &mut *self.slot(index) })
+    }
+
+    /// Itera sobre os chunks como slices, sem nenhuma cópia. O último pode estar pela metade.
+    pub(crate) fn chunks(&self) -> Chunks<'_, T> {
+        Chunks {
+            vector: self,
+            chunk: 0,
+        }
+    }
+
+    pub(crate) fn chunks_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let length = self.len();
+
+        (0..self.chunk_count())
+            .map(|chunk| (chunk, Self::chunk_start(chunk)))
+            .take_while(move |&(_, start)| start < length)
+            .map(move |(chunk, start)| {
+                let size = Self::chunk_capacity(chunk).min(length - start);
+                // cada chunk é um bloco separado, então os slices nunca se sobrepõem.
+                unsafe { std::slice::from_raw_parts_mut(self.chunk_ptr(chunk).as_ptr(), size) }
+            })
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> {
+        self.chunks().flatten()
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.chunks_mut().flatten()
+    }
+}
+
+/// Iterador criado por [`SegmentedVector::chunks`].
+pub(crate) struct Chunks<'a, T> {
+    vector: &'a SegmentedVector<T>,
+    chunk: usize,
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<&'a [T]> {
+        let start = SegmentedVector::<T>::chunk_start(self.chunk);
+        let length = self.vector.len();
+
+        if start >= length {
+            return None;
+        }
+
+        let size = SegmentedVector::<T>::chunk_capacity(self.chunk).min(length - start);
+        let ptr = self.vector.chunk_ptr(self.chunk);
+        self.chunk += 1;
+
+        Some(unsafe { std::slice::from_raw_parts(ptr.as_ptr(), size) })
+    }
+}
+
+impl<T> Index<usize> for SegmentedVector<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.len();
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T> IndexMut<usize> for SegmentedVector<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.len();
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T> Default for SegmentedVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for SegmentedVector<T> {
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+
+        if Self::IS_ZST {
+            return;
+        }
+
+        for (chunk, ptr) in self.chunks.get_mut().iter().enumerate() {
+            let layout = Layout::array::<T>(Self::chunk_capacity(chunk)).unwrap();
+            unsafe { Global.deallocate(ptr.cast(), layout) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_push_pop_index() {
+        let mut v = SegmentedVector::new();
+        for i in 0..100 {
+            v.push(i);
+        }
+
+        assert_eq!(v.len(), 100);
+        // 8 + 16 + 32 + 64
+        assert_eq!(v.capacity(), 120);
+
+        for i in 0..100 {
+            assert_eq!(v[i], i);
+        }
+        assert_eq!(v.get(100), None);
+
+        v[50] = 500;
+        assert_eq!(v.get(50), Some(&500));
+
+        assert_eq!(v.pop(), Some(99));
+        assert_eq!(v.len(), 99);
+        // a capacidade não diminui
+        assert_eq!(v.capacity(), 120);
+    }
+
+    #[test]
+    fn test_locate() {
+        assert_eq!(SegmentedVector::<u8>::locate(0), (0, 0));
+        assert_eq!(SegmentedVector::<u8>::locate(7), (0, 7));
+        assert_eq!(SegmentedVector::<u8>::locate(8), (1, 0));
+        assert_eq!(SegmentedVector::<u8>::locate(23), (1, 15));
+        assert_eq!(SegmentedVector::<u8>::locate(24), (2, 0));
+    }
+
+    #[test]
+    fn test_stable_addresses() {
+        let v = SegmentedVector::new();
+        let first: &String = v.push(String::from("first"));
+        let address = first as *const String;
+
+        // muitos pushes depois, a referência continua apontando pro mesmo lugar.
+        // com um `Vector` isso nem compilaria, e se compilasse seria use-after-free.
+        for i in 0..1000 {
+            v.push(format!("String {}", i));
+        }
+
+        assert_eq!(first, "first");
+        assert_eq!(&v[0] as *const String, address);
+    }
+
+    #[test]
+    fn test_interner() {
+        // um interner simples: guarda cada string uma vez e entrega `&str` estáveis
+        struct Interner {
+            strings: SegmentedVector<String>,
+        }
+
+        impl Interner {
+            fn intern(&self, name: &str) -> &str {
+                match self.strings.iter().find(|s| *s == name) {
+                    Some(existing) => existing,
+                    None => self.strings.push(name.to_string()),
+                }
+            }
+        }
+
+        let interner = Interner {
+            strings: SegmentedVector::new(),
+        };
+        let a = interner.intern("a");
+        let b = interner.intern("b");
+        for i in 0..100 {
+            interner.intern(&i.to_string());
+        }
+
+        assert!(std::ptr::eq(a, interner.intern("a")));
+        assert_eq!(b, "b");
+        assert_eq!(interner.strings.len(), 102);
+    }
+
+    #[test]
+    fn test_chunks_and_iter() {
+        let mut v = SegmentedVector::new();
+        for i in 0..30 {
+            v.push(i);
+        }
+
+        let sizes: Vec<usize> = v.chunks().map(|chunk| chunk.len()).collect();
+        assert_eq!(sizes, [8, 16, 6]);
+
+        for n in v.iter_mut() {
+            *n *= 2;
+        }
+        let collected: Vec<i32> = v.iter().copied().collect();
+        assert_eq!(collected, (0..30).map(|n| n * 2).collect::<Vec<_>>());
+
+        for chunk in v.chunks_mut() {
+            chunk.reverse();
+        }
+        assert_eq!(v[0], 14);
+        assert_eq!(v[8], 46);
+
+        let empty: SegmentedVector<i32> = SegmentedVector::new();
+        assert_eq!(empty.chunks().count(), 0);
+    }
+
+    #[test]
+    fn test_drop() {
+        let tracker = Rc::new(());
+
+        {
+            let mut v = SegmentedVector::new();
+            for _ in 0..50 {
+                v.push(Rc::clone(&tracker));
+            }
+            drop(v.pop());
+            assert_eq!(Rc::strong_count(&tracker), 50);
+        }
+
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut v = SegmentedVector::new();
+        for _ in 0..100 {
+            v.push(());
+        }
+
+        assert_eq!(v.len(), 100);
+        assert_eq!(v.iter().count(), 100);
+        assert_eq!(v.pop(), Some(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn test_index_out_of_bounds() {
+        let v = SegmentedVector::new();
+        v.push(1);
+        let _ = v[1];
+    }
+}
@@ -0,0 +1,253 @@
+#![allow(unused)]
+
+use crate::vector::Vector;
+use std::{
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+/// Vetor com otimização de buffer pequeno (small buffer optimization).
+///
+/// Os primeiros `N` elementos ficam guardados dentro da própria estrutura (na stack, se ela
+/// estiver na stack). Só quando passamos de `N` é que os elementos "transbordam" pra HEAP,
+/// e a partir daí tudo funciona exatamente como um [`Vector`].
+pub(crate) struct SmallVector<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+enum Storage<T, const N: usize> {
+    // `MaybeUninit` porque só as primeiras `length` posições estão inicializadas.
+    // um `[T; N]` exigiria que todas existissem desde o começo.
+    Inline {
+        buffer: [MaybeUninit<T>; N],
+        length: usize,
+    },
+    Heap(Vector<T>),
+}
+
+impl<T, const N: usize> SmallVector<T, N> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            storage: Storage::Inline {
+                // um array de `MaybeUninit` não precisa de inicialização nenhuma.
+                buffer: [const { MaybeUninit::uninit() }; N],
+                length: 0,
+            },
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { length, .. } => *length,
+            Storage::Heap(vector) => vector.len(),
+        }
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => N,
+            Storage::Heap(vector) => vector.capacity(),
+        }
+    }
+
+    /// Se os elementos já foram movidos pra HEAP.
+    pub(crate) const fn spilled(&self) -> bool {
+        matches!(self.storage, Storage::Heap(_))
+    }
+
+    pub(crate) fn push(&mut self, element: T) {
+        match &mut self.storage {
+            Storage::Inline { buffer, length } if *length < N => {
+                buffer[*length].write(element);
+                *length += 1;
+            }
+            Storage::Inline { .. } => {
+                self.spill();
+                self.push(element);
+            }
+            Storage::Heap(vector) => vector.push(element),
+        }
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        match &mut self.storage {
+            Storage::Inline { length: 0, .. } => None,
+            Storage::Inline { buffer, length } => {
+                *length -= 1;
+                // a posição `length` estava inicializada e agora passa a ser considerada lixo,
+                // então podemos mover o valor pra fora.
+                Some(unsafe { buffer[*length].assume_init_read() })
+            }
+            Storage::Heap(vector) => vector.pop(),
+        }
+    }
+
+    // move tudo que está inline pra um `Vector` na HEAP. a partir daqui o crescimento segue
+    // a mesma política do `Vector`: o próximo `push` já vai dobrar a capacidade.
+    fn spill(&mut self) {
+        let Storage::Inline { buffer, length } = &mut self.storage else {
+            return;
+        };
+
+        // alocamos antes de mexer em qualquer elemento: se a alocação falhar (panic),
+        // o buffer inline continua intacto.
+        let mut vector = Vector::with_capacity(N);
+        for slot in &buffer[..*length] {
+            // esse push nunca realoca, então não tem como dar panic no meio da cópia.
+            vector.push(unsafe { slot.assume_init_read() });
+        }
+
+        // os elementos agora pertencem ao vetor. zerar o tamanho garante que
+        // eles não sejam dropados duas vezes.
+        *length = 0;
+        self.storage = Storage::Heap(vector);
+    }
+}
+
+impl<T, const N: usize> Deref for SmallVector<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.storage {
+            Storage::Inline { buffer, length } => unsafe {
+                std::slice::from_raw_parts(buffer.as_ptr().cast::<T>(), *length)
+            },
+            Storage::Heap(vector) => vector,
+        }
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallVector<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.storage {
+            Storage::Inline { buffer, length } => unsafe {
+                std::slice::from_raw_parts_mut(buffer.as_mut_ptr().cast::<T>(), *length)
+            },
+            Storage::Heap(vector) => vector,
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallVector<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for SmallVector<T, N> {
+    fn drop(&mut self) {
+        // o `Vector` já sabe se dropar sozinho. no caso inline o compilador não sabe
+        // quais posições do `MaybeUninit` estão vivas, então precisamos fazer na mão.
+        if let Storage::Inline { buffer, length } = &mut self.storage {
+            let initialized =
+                ptr::slice_from_raw_parts_mut(buffer.as_mut_ptr().cast::<T>(), *length);
+            *length = 0;
+            unsafe { ptr::drop_in_place(initialized) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_inline_push_pop() {
+        let mut v: SmallVector<i32, 4> = SmallVector::new();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+
+        assert!(!v.spilled());
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.capacity(), 4);
+        assert_eq!(&v[..], &[1, 2, 3]);
+
+        assert_eq!(v.pop(), Some(3));
+        assert_eq!(v.pop(), Some(2));
+        assert_eq!(v.pop(), Some(1));
+        assert_eq!(v.pop(), None);
+    }
+
+    #[test]
+    fn test_spill_to_heap() {
+        let mut v: SmallVector<String, 2> = SmallVector::new();
+        v.push(String::from("a"));
+        v.push(String::from("b"));
+        assert!(!v.spilled());
+
+        // o terceiro elemento não cabe mais inline
+        v.push(String::from("c"));
+        assert!(v.spilled());
+        // mesma política do `Vector`: dobra a partir de N
+        assert_eq!(v.capacity(), 4);
+        assert_eq!(&v[..], &["a", "b", "c"]);
+
+        // deref_mut funciona nos dois modos
+        v[0].push('!');
+        assert_eq!(v.pop().as_deref(), Some("c"));
+        assert_eq!(&v[..], &["a!", "b"]);
+
+        // depois de transbordar continua na heap, mesmo com poucos elementos
+        v.pop();
+        v.pop();
+        assert!(v.spilled());
+        assert_eq!(v.len(), 0);
+    }
+
+    #[test]
+    fn test_zero_inline_capacity() {
+        let mut v: SmallVector<u8, 0> = SmallVector::new();
+        assert_eq!(v.capacity(), 0);
+
+        v.push(1);
+        assert!(v.spilled());
+        assert_eq!(&v[..], &[1]);
+    }
+
+    #[test]
+    fn test_drop_inline_and_spilled() {
+        // o `Rc` conta quantas referências ainda existem. se algum elemento for dropado
+        // duas vezes (ou nenhuma), o contador vai acusar.
+        let tracker = Rc::new(());
+
+        {
+            let mut v: SmallVector<Rc<()>, 3> = SmallVector::new();
+            v.push(Rc::clone(&tracker));
+            v.push(Rc::clone(&tracker));
+            assert_eq!(Rc::strong_count(&tracker), 3);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        {
+            let mut v: SmallVector<Rc<()>, 3> = SmallVector::new();
+            for _ in 0..3 {
+                v.push(Rc::clone(&tracker));
+            }
+            assert!(!v.spilled());
+
+            // a transição não pode clonar nem dropar nada, só mover
+            v.push(Rc::clone(&tracker));
+            assert!(v.spilled());
+            assert_eq!(Rc::strong_count(&tracker), 5);
+
+            drop(v.pop());
+            assert_eq!(Rc::strong_count(&tracker), 4);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    #[test]
+    fn test_zero_sized() {
+        let mut v: SmallVector<(), 2> = SmallVector::new();
+        for _ in 0..10 {
+            v.push(());
+        }
+
+        assert!(v.spilled());
+        assert_eq!(v.len(), 10);
+        assert_eq!(v.capacity(), usize::MAX);
+    }
+}
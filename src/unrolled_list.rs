@@ -0,0 +1,777 @@
+#![allow(unused)]
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Index, IndexMut},
+    ptr::{self, NonNull},
+    slice,
+};
+
+/// Lista encadeada "desenrolada": cada node guarda até `B` elementos num array, em vez de um só.
+///
+/// É um meio termo entre a [`LinkedList`](crate::linked_list::LinkedList) e o
+/// [`Vector`](crate::vector::Vector). Os elementos de um mesmo node ficam contíguos na memória,
+/// então percorrer a lista é bem mais amigável pro cache, e acessar um índice pula `B` elementos
+/// por node. Já inserir ou remover no meio só desloca os elementos de um node, nunca da lista
+/// toda. Com `B` perto de `sqrt(n)`, as duas coisas ficam em O(sqrt(n)).
+///
+/// Todo node, menos o último, mantém pelo menos `B / 2` elementos: quando enche ele é dividido
+/// ao meio, e quando fica abaixo disso pega elementos do vizinho ou se funde com ele.
+pub(crate) struct UnrolledList<T, const B: usize> {
+    head: Link<T, B>,
+    // guardamos a tail pro `push_back` não precisar percorrer a lista.
+    tail: Link<T, B>,
+    length: usize,
+    marker: PhantomData<Box<Node<T, B>>>,
+}
+
+struct Node<T, const B: usize> {
+    // igual ao `SmallVector`: só as primeiras `length` posições estão inicializadas.
+    elements: [MaybeUninit<T>; B],
+    length: usize,
+    next: Link<T, B>,
+}
+
+// head e tail podem apontar pro mesmo node, então usamos ponteiros crus como na
+// `DoublyLinkedList` em vez de `Box`.
+type Link<T, const B: usize> = Option<NonNull<Node<T, B>>>;
+
+unsafe impl<T: Send, const B: usize> Send for UnrolledList<T, B> {}
+unsafe impl<T: Sync, const B: usize> Sync for UnrolledList<T, B> {}
+
+impl<T, const B: usize> Node<T, B> {
+    fn allocate() -> NonNull<Self> {
+        let node = Box::new(Self {
+            elements: [const { MaybeUninit::uninit() }; B],
+            length: 0,
+            next: None,
+        });
+
+        NonNull::from(Box::leak(node))
+    }
+
+    const fn is_full(&self) -> bool {
+        self.length == B
+    }
+
+    fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.elements.as_ptr().cast(), self.length) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.elements.as_mut_ptr().cast(), self.length) }
+    }
+
+    // insere em `offset`, empurrando o resto uma posição pra direita. precisa ter espaço.
+    fn insert(&mut self, offset: usize, element: T) {
+        debug_assert!(!self.is_full() && offset <= self.length);
+
+        unsafe {
+            let slot = self.elements.as_mut_ptr().add(offset).cast::<T>();
+            ptr::copy(slot, slot.add(1), self.length - offset);
+            slot.write(element);
+        }
+
+        self.length += 1;
+    }
+
+    // tira o elemento em `offset`, puxando o resto uma posição pra esquerda.
+    fn remove(&mut self, offset: usize) -> T {
+        debug_assert!(offset < self.length);
+
+        self.length -= 1;
+        unsafe {
+            let slot = self.elements.as_mut_ptr().add(offset).cast::<T>();
+            let element = slot.read();
+            ptr::copy(slot.add(1), slot, self.length - offset);
+            element
+        }
+    }
+
+    // move os elementos a partir de `from` pro final de `other`, que precisa ter espaço.
+    // é o que usamos tanto pra dividir um node cheio quanto pra fundir dois nodes.
+    fn move_tail_to(&mut self, from: usize, other: &mut Self) {
+        let count = self.length - from;
+        debug_assert!(other.length + count <= B);
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.elements.as_ptr().add(from),
+                other.elements.as_mut_ptr().add(other.length),
+                count,
+            );
+        }
+
+        self.length = from;
+        other.length += count;
+    }
+}
+
+impl<T, const B: usize> UnrolledList<T, B> {
+    pub(crate) const fn new() -> Self {
+        // com um elemento por node não teria como dividir ao meio. pra isso existe a `LinkedList`.
+        const {
+            assert!(
+                B >= 2,
+                "an unrolled list needs at least 2 elements per node"
+            )
+        };
+
+        Self {
+            head: None,
+            tail: None,
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) const fn len(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub(crate) fn push_back(&mut self, element: T) {
+        let tail = match self.tail {
+            Some(tail) if unsafe { !(*tail.as_ptr()).is_full() } => tail,
+            // sem tail, ou a tail já está cheia: começa um node novo no final.
+            previous => {
+                let node = Node::allocate();
+                match previous {
+                    Some(previous) => unsafe { (*previous.as_ptr()).next = Some(node) },
+                    None => self.head = Some(node),
+                }
+
+                self.tail = Some(node);
+                node
+            }
+        };
+
+        let tail = unsafe { &mut *tail.as_ptr() };
+        tail.insert(tail.length, element);
+        self.length += 1;
+    }
+
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.length {
+            return None;
+        }
+
+        let (_, node, offset) = self.locate(index);
+        Some(unsafe { &(*node.as_ptr()).as_slice()[offset] })
+    }
+
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.length {
+            return None;
+        }
+
+        let (_, node, offset) = self.locate(index);
+        Some(unsafe { &mut (*node.as_ptr()).as_mut_slice()[offset] })
+    }
+
+    /// Insere `element` na posição `index`. Só desloca elementos do node onde ele entra;
+    /// se o node estiver cheio, ele é dividido ao meio antes.
+    pub(crate) fn insert(&mut self, index: usize, element: T) {
+        let length = self.length;
+        assert!(
+            index <= length,
+            "insertion index (is {index}) should be <= len (is {length})"
+        );
+
+        if index == length {
+            return self.push_back(element);
+        }
+
+        let (_, node_ptr, offset) = self.locate(index);
+        let node = unsafe { &mut *node_ptr.as_ptr() };
+
+        if node.is_full() {
+            // metade de cima vai pra um node novo, logo depois desse.
+            let half = B / 2;
+            let new_ptr = Node::allocate();
+            let new = unsafe { &mut *new_ptr.as_ptr() };
+
+            node.move_tail_to(half, new);
+            new.next = node.next;
+            node.next = Some(new_ptr);
+            if self.tail == Some(node_ptr) {
+                self.tail = Some(new_ptr);
+            }
+
+            match offset <= half {
+                true => node.insert(offset, element),
+                false => new.insert(offset - half, element),
+            }
+        } else {
+            node.insert(offset, element);
+        }
+
+        self.length += 1;
+    }
+
+    /// Remove e retorna o elemento na posição `index`. Se o node ficar com menos de `B / 2`
+    /// elementos, ele pega um elemento do próximo ou se funde com ele.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        let length = self.length;
+        assert!(
+            index < length,
+            "removal index (is {index}) should be < len (is {length})"
+        );
+
+        let (prev, node, offset) = self.locate(index);
+        let element = unsafe { (*node.as_ptr()).remove(offset) };
+        self.length -= 1;
+
+        unsafe { self.rebalance(prev, node) };
+        element
+    }
+
+    pub(crate) fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T, B> {
+        Iter {
+            node: self.head,
+            elements: Default::default(),
+            remaining: self.length,
+        }
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T, B> {
+        IterMut {
+            node: self.head,
+            elements: Default::default(),
+            remaining: self.length,
+        }
+    }
+
+    /// Quantidade de nodes alocados.
+    pub(crate) fn node_count(&self) -> usize {
+        let mut count = 0;
+        let mut current = self.head;
+        while let Some(node) = current {
+            count += 1;
+            current = unsafe { (*node.as_ptr()).next };
+        }
+
+        count
+    }
+
+    // acha o node que guarda o elemento `index`, o node anterior e a posição dentro do node.
+    // pula um node inteiro por passo, por isso é O(n / B). `index` precisa ser válido.
+    fn locate(&self, mut index: usize) -> (Link<T, B>, NonNull<Node<T, B>>, usize) {
+        let mut prev = None;
+        let mut current = self.head;
+
+        while let Some(node) = current {
+            let length = unsafe { (*node.as_ptr()).length };
+            if index < length {
+                return (prev, node, index);
+            }
+
+            index -= length;
+            prev = current;
+            current = unsafe { (*node.as_ptr()).next };
+        }
+
+        unreachable!("index should have been checked against the length")
+    }
+
+    // chamado depois de remover um elemento de `node`, pra manter os nodes pelo menos meio
+    // cheios. `prev` é o node anterior a `node` (ou `None` se ele for a head).
+    unsafe fn rebalance(&mut self, prev: Link<T, B>, node_ptr: NonNull<Node<T, B>>) {
+        let node = unsafe { &mut *node_ptr.as_ptr() };
+        if node.length >= B / 2 {
+            return;
+        }
+
+        match node.next {
+            Some(next_ptr) => {
+                let next = unsafe { &mut *next_ptr.as_ptr() };
+
+                if node.length + next.length <= B {
+                    // cabe tudo num node só: traz os elementos do próximo e libera ele.
+                    next.move_tail_to(0, node);
+                    unsafe { self.unlink_after(Some(node_ptr), next_ptr) };
+                } else {
+                    // o próximo tem de sobra, então pegamos só um emprestado.
+                    let borrowed = next.remove(0);
+                    node.insert(node.length, borrowed);
+                }
+            }
+            // o último node pode ficar com poucos elementos, só não pode ficar vazio.
+            None if node.length == 0 => unsafe { self.unlink_after(prev, node_ptr) },
+            None => {}
+        }
+    }
+
+    // tira `node` (que vem logo depois de `prev`) da lista e libera ele.
+    // os elementos dele já precisam ter saído.
+    unsafe fn unlink_after(&mut self, prev: Link<T, B>, node: NonNull<Node<T, B>>) {
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+        debug_assert_eq!(node.length, 0);
+
+        match prev {
+            Some(prev) => unsafe { (*prev.as_ptr()).next = node.next },
+            None => self.head = node.next,
+        }
+        if node.next.is_none() {
+            self.tail = prev;
+        }
+    }
+}
+
+impl<T, const B: usize> Drop for UnrolledList<T, B> {
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+        self.tail = None;
+
+        while let Some(node) = current {
+            let mut node = unsafe { Box::from_raw(node.as_ptr()) };
+            current = node.next;
+
+            // igual ao `SmallVector`: o compilador não sabe quais posições do `MaybeUninit`
+            // estão vivas, então dropamos na mão. o `Box` do node é liberado logo depois.
+            let initialized: *mut [T] = node.as_mut_slice();
+            node.length = 0;
+            unsafe { ptr::drop_in_place(initialized) }
+        }
+    }
+}
+
+impl<T, const B: usize> Index<usize> for UnrolledList<T, B> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let length = self.length;
+        self.get(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T, const B: usize> IndexMut<usize> for UnrolledList<T, B> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let length = self.length;
+        self.get_mut(index).unwrap_or_else(|| {
+            panic!("index out of bounds: the len is {length} but the index is {index}")
+        })
+    }
+}
+
+impl<T, const B: usize> Default for UnrolledList<T, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const B: usize> Clone for UnrolledList<T, B> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: fmt::Debug, const B: usize> fmt::Debug for UnrolledList<T, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq, const B: usize> PartialEq for UnrolledList<T, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.length == other.length && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq, const B: usize> Eq for UnrolledList<T, B> {}
+
+impl<T, const B: usize> FromIterator<T> for UnrolledList<T, B> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T, const B: usize> Extend<T> for UnrolledList<T, B> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push_back(element);
+        }
+    }
+}
+
+impl<T, const B: usize> IntoIterator for UnrolledList<T, B> {
+    type Item = T;
+    type IntoIter = IntoIter<T, B>;
+
+    fn into_iter(self) -> IntoIter<T, B> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T, const B: usize> IntoIterator for &'a UnrolledList<T, B> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, B>;
+
+    fn into_iter(self) -> Iter<'a, T, B> {
+        self.iter()
+    }
+}
+
+impl<'a, T, const B: usize> IntoIterator for &'a mut UnrolledList<T, B> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T, B>;
+
+    fn into_iter(self) -> IterMut<'a, T, B> {
+        self.iter_mut()
+    }
+}
+
+/// Iterador que empresta os elementos da lista. Anda pelos elementos de um node como num
+/// slice e só segue o ponteiro quando o node acaba.
+pub(crate) struct Iter<'a, T, const B: usize> {
+    node: Link<T, B>,
+    elements: slice::Iter<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T, const B: usize> Iterator for Iter<'a, T, B> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                self.remaining -= 1;
+                return Some(element);
+            }
+
+            let node = unsafe { &*self.node?.as_ptr() };
+            self.elements = node.as_slice().iter();
+            self.node = node.next;
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const B: usize> ExactSizeIterator for Iter<'_, T, B> {}
+
+/// Iterador que empresta os elementos da lista de forma mutável.
+pub(crate) struct IterMut<'a, T, const B: usize> {
+    node: Link<T, B>,
+    elements: slice::IterMut<'a, T>,
+    remaining: usize,
+}
+
+impl<'a, T, const B: usize> Iterator for IterMut<'a, T, B> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                self.remaining -= 1;
+                return Some(element);
+            }
+
+            let node = unsafe { &mut *self.node?.as_ptr() };
+            self.node = node.next;
+            self.elements = node.as_mut_slice().iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T, const B: usize> ExactSizeIterator for IterMut<'_, T, B> {}
+
+/// Iterador que consome a lista.
+pub(crate) struct IntoIter<T, const B: usize>(UnrolledList<T, B>);
+
+impl<T, const B: usize> Iterator for IntoIter<T, B> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        // remover do começo só desloca os elementos do primeiro node, O(B).
+        match self.0.is_empty() {
+            true => None,
+            false => Some(self.0.remove(0)),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<T, const B: usize> ExactSizeIterator for IntoIter<T, B> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linked_list::LinkedList, vector::Vector};
+    use std::{hint::black_box, rc::Rc, time::Instant};
+
+    // xorshift simples, só pra gerar posições reproduzíveis
+    fn pseudo_random(count: usize, mut seed: u64) -> Vec<usize> {
+        (0..count)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed as usize
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_push_and_index() {
+        let mut list: UnrolledList<i32, 4> = UnrolledList::new();
+        assert!(list.is_empty());
+        assert_eq!(list.get(0), None);
+
+        for i in 0..10 {
+            list.push_back(i);
+        }
+
+        // 10 elementos em nodes de 4: 4 + 4 + 2
+        assert_eq!(list.len(), 10);
+        assert_eq!(list.node_count(), 3);
+        assert_eq!(list[0], 0);
+        assert_eq!(list[9], 9);
+        assert_eq!(list.get(10), None);
+
+        list[5] *= 10;
+        assert_eq!(list.get(5), Some(&50));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn test_index_out_of_bounds() {
+        let list: UnrolledList<i32, 4> = (0..2).collect();
+        let _ = list[2];
+    }
+
+    #[test]
+    fn test_insert_splits_full_nodes() {
+        let mut list: UnrolledList<i32, 4> = (0..4).collect();
+        assert_eq!(list.node_count(), 1);
+
+        // o node está cheio, então ele é dividido em [0, 1] e [2, 3] antes de inserir
+        list.insert(1, 10);
+        assert_eq!(list.node_count(), 2);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 10, 1, 2, 3]);
+
+        list.insert(5, 20);
+        list.insert(0, -1);
+        assert_eq!(
+            list.iter().copied().collect::<Vec<_>>(),
+            [-1, 0, 10, 1, 2, 3, 20]
+        );
+        assert_eq!(list.len(), 7);
+    }
+
+    #[test]
+    fn test_remove_borrows_and_merges() {
+        let mut list: UnrolledList<i32, 4> = (0..8).collect();
+        assert_eq!(list.node_count(), 2);
+
+        // o primeiro node fica com 1 elemento e pega um emprestado do segundo
+        assert_eq!(list.remove(0), 0);
+        assert_eq!(list.remove(0), 1);
+        assert_eq!(list.remove(0), 2);
+        assert_eq!(list.node_count(), 2);
+
+        // agora os dois cabem num node só, então eles se fundem
+        assert_eq!(list.remove(0), 3);
+        assert_eq!(list.node_count(), 1);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [4, 5, 6, 7]);
+
+        // o último node some quando fica vazio
+        while !list.is_empty() {
+            list.remove(list.len() - 1);
+        }
+        assert_eq!(list.node_count(), 0);
+
+        // e a lista continua utilizável depois disso
+        list.push_back(1);
+        list.insert(0, 0);
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 3) should be < len (is 3)")]
+    fn test_remove_out_of_bounds() {
+        let mut list: UnrolledList<i32, 4> = (0..3).collect();
+        list.remove(3);
+    }
+
+    #[test]
+    fn test_matches_vec_on_random_edits() {
+        let mut list: UnrolledList<usize, 5> = UnrolledList::new();
+        let mut reference = Vec::new();
+
+        for (step, random) in pseudo_random(5000, 99).into_iter().enumerate() {
+            if step % 3 == 2 && !reference.is_empty() {
+                let index = random % reference.len();
+                assert_eq!(list.remove(index), reference.remove(index));
+            } else {
+                let index = random % (reference.len() + 1);
+                list.insert(index, step);
+                reference.insert(index, step);
+            }
+        }
+
+        assert_eq!(list.len(), reference.len());
+        assert!(list.iter().eq(reference.iter()));
+        for (index, expected) in reference.iter().enumerate() {
+            assert_eq!(list.get(index), Some(expected));
+        }
+
+        // nenhum node (fora o último) fica com menos da metade
+        let minimum_nodes = reference.len().div_ceil(5);
+        assert!(list.node_count() <= 2 * minimum_nodes + 1);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut list: UnrolledList<String, 3> =
+            ["a", "b", "c", "d"].into_iter().map(String::from).collect();
+
+        for s in &mut list {
+            s.push('!');
+        }
+        assert_eq!(format!("{list:?}"), r#"["a!", "b!", "c!", "d!"]"#);
+        assert_eq!(list.iter().len(), 4);
+
+        let cloned = list.clone();
+        assert_eq!(cloned, list);
+
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.next().as_deref(), Some("a!"));
+        assert_eq!(into_iter.len(), 3);
+        assert_eq!(into_iter.collect::<Vec<_>>(), ["b!", "c!", "d!"]);
+    }
+
+    #[test]
+    fn test_drop() {
+        let tracker = Rc::new(());
+
+        {
+            let mut list: UnrolledList<Rc<()>, 4> = (0..10).map(|_| Rc::clone(&tracker)).collect();
+            list.insert(3, Rc::clone(&tracker));
+            drop(list.remove(7));
+            assert_eq!(Rc::strong_count(&tracker), 11);
+
+            let mut into_iter = list.into_iter();
+            into_iter.next();
+            assert_eq!(Rc::strong_count(&tracker), 10);
+        }
+        assert_eq!(Rc::strong_count(&tracker), 1);
+
+        let mut list: UnrolledList<Rc<()>, 4> = (0..10).map(|_| Rc::clone(&tracker)).collect();
+        list.clear();
+        assert!(list.is_empty());
+        assert_eq!(Rc::strong_count(&tracker), 1);
+    }
+
+    // benchmarks simples contra `Vector` e `LinkedList`. ficam ignorados porque só fazem sentido
+    // com otimizações: `cargo test --release unrolled_list -- --ignored --nocapture`.
+    const BENCH_SIZE: usize = 100_000;
+
+    fn report(name: &str, start: Instant) {
+        println!("{name:<40} {:?}", start.elapsed());
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_iteration() {
+        let unrolled: UnrolledList<usize, 64> = (0..BENCH_SIZE).collect();
+        let vector: Vector<usize> = (0..BENCH_SIZE).collect();
+        let linked: LinkedList<usize> = (0..BENCH_SIZE).collect();
+
+        for _ in 0..3 {
+            let start = Instant::now();
+            black_box(unrolled.iter().sum::<usize>());
+            report("iteration / UnrolledList<_, 64>", start);
+
+            let start = Instant::now();
+            black_box(vector.iter().sum::<usize>());
+            report("iteration / Vector", start);
+
+            let start = Instant::now();
+            black_box(linked.iter().sum::<usize>());
+            report("iteration / LinkedList", start);
+        }
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_middle_insertion() {
+        const INSERTIONS: usize = 10_000;
+
+        let mut unrolled: UnrolledList<usize, 64> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for i in 0..INSERTIONS {
+            unrolled.insert(unrolled.len() / 2, i);
+        }
+        report("middle insertion / UnrolledList<_, 64>", start);
+
+        let mut vector: Vector<usize> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for i in 0..INSERTIONS {
+            vector.insert(vector.len() / 2, i);
+        }
+        report("middle insertion / Vector", start);
+
+        // a `LinkedList` não tem insert por índice; andar até o meio já é o custo todo.
+        let mut linked: LinkedList<usize> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for i in 0..INSERTIONS {
+            let mut tail = linked.split_off(linked.len() / 2);
+            tail.push(i);
+            linked.append(&mut tail);
+        }
+        report("middle insertion / LinkedList", start);
+
+        assert_eq!(unrolled.len(), vector.len());
+        assert_eq!(vector.len(), linked.len());
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_random_access() {
+        let positions = pseudo_random(10_000, 3);
+
+        let unrolled: UnrolledList<usize, 64> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for &position in &positions {
+            black_box(unrolled[position % BENCH_SIZE]);
+        }
+        report("random access / UnrolledList<_, 64>", start);
+
+        let vector: Vector<usize> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for &position in &positions {
+            black_box(vector[position % BENCH_SIZE]);
+        }
+        report("random access / Vector", start);
+
+        let linked: LinkedList<usize> = (0..BENCH_SIZE).collect();
+        let start = Instant::now();
+        for &position in &positions {
+            black_box(linked.iter().nth(position % BENCH_SIZE));
+        }
+        report("random access / LinkedList", start);
+    }
+}
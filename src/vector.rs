@@ -1,59 +1,112 @@
 #![allow(unused)]
 
+use crate::{
+    allocator::{Global, RawAlloc},
+    raw_buffer::{RawBuffer, handle_reserve},
+};
 use std::{
-    alloc::{self, Layout},
-    ops::{Deref, DerefMut},
-    ptr::{self, NonNull},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds},
+    ptr,
 };
 
+pub use crate::raw_buffer::{GrowthPolicy, TryReserveError};
+
 /// Vetor (lista dinâmica alocada na HEAP)
-struct Vector<T> {
-    // ponteiro que aponta para o conteúdo da célula.
-    // em rust, usamos `NonNull` para indicar pro compilador que esse ponteiro
-    // nunca deve ser nulo.
-    // isso também abre portas para mais otimizações por parte do compilador.
-    ptr: NonNull<T>,
-    capacity: usize,
+///
+/// Por padrão usa o alocador global, mas qualquer [`RawAlloc`] pode ser passado com `new_in`.
+pub struct Vector<T, A: RawAlloc = Global> {
+    // toda a parte de alocar, crescer e liberar memória fica no `RawBuffer`.
+    // o vetor só precisa saber quantas posições do começo do buffer estão ocupadas.
+    buf: RawBuffer<T, A>,
     length: usize,
 }
 
-// rust é paranoico com threads. ponteiros (*mut T) não implementam send/sync automaticamente
-// porque o compilador não sabe se é seguro. estamos basicamente dizendo "confia no pai".
-unsafe impl<T: Send> Send for Vector<T> {}
-unsafe impl<T: Sync> Sync for Vector<T> {}
-
 impl<T> Vector<T> {
-    fn new() -> Self {
-        // precisamos fazer esse assert porque rust tem lida diferente com valores de tamanho zero.
-        assert!(
-            std::mem::size_of::<T>() != 0,
-            "Zero-sized type are not supported >:X"
-        );
+    pub fn new() -> Self {
+        Self::new_in(Global)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_in(capacity, Global)
+    }
 
+    /// Cria um vetor com espaço para pelo menos `capacity` elementos, devolvendo um erro
+    /// em vez de abortar caso a alocação falhe.
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+}
+
+impl<T, A: RawAlloc> Vector<T, A> {
+    pub fn new_in(alloc: A) -> Self {
         Self {
-            // `dangling` cria um ponteiro não-nulo invalido mas alinhado.
-            // o que é seguro, a não ser que a gente o deferencie.
-            ptr: NonNull::dangling(),
-            capacity: 0,
+            buf: RawBuffer::new_in(alloc),
             length: 0,
         }
     }
 
-    const fn len(&self) -> usize {
+    /// Troca a estratégia de crescimento do vetor. Não realoca nada na hora.
+    pub fn with_growth_policy(mut self, growth: GrowthPolicy) -> Self {
+        self.buf.set_growth_policy(growth);
+        self
+    }
+
+    pub const fn growth_policy(&self) -> GrowthPolicy {
+        self.buf.growth_policy()
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        handle_reserve(Self::try_with_capacity_in(capacity, alloc))
+    }
+
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(Self {
+            buf: RawBuffer::try_with_capacity_in(capacity, alloc)?,
+            length: 0,
+        })
+    }
+
+    /// O alocador usado por esse vetor.
+    pub const fn allocator(&self) -> &A {
+        self.buf.allocator()
+    }
+
+    pub const fn len(&self) -> usize {
         self.length
     }
 
-    const fn capacity(&self) -> usize {
-        self.capacity
+    pub const fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub const fn capacity(&self) -> usize {
+        self.buf.capacity()
     }
 
-    fn push(&mut self, element: T) {
-        if self.length == self.capacity {
-            self.grow();
+    pub const fn as_ptr(&self) -> *const T {
+        self.buf.ptr()
+    }
+
+    pub const fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.ptr()
+    }
+
+    pub fn push(&mut self, element: T) {
+        handle_reserve(self.try_push(element))
+    }
+
+    /// Igual ao `push`, mas se não houver memória pra crescer o vetor devolve um erro e o
+    /// vetor continua intacto.
+    pub fn try_push(&mut self, element: T) -> Result<(), TryReserveError> {
+        if self.length == self.capacity() {
+            self.try_reserve(1)?;
         }
 
         unsafe {
-            let end = self.ptr.as_ptr().add(self.length);
+            let end = self.buf.ptr().add(self.length);
 
             // em rust, não podemos fazer *end = element.
             // ele tentaria executar o `drop` no lixo que está na memória
@@ -63,9 +116,10 @@ impl<T> Vector<T> {
         }
 
         self.length += 1;
+        Ok(())
     }
 
-    fn pop(&mut self) -> Option<T> {
+    pub fn pop(&mut self) -> Option<T> {
         if self.length == 0 {
             return None;
         }
@@ -73,150 +127,1620 @@ impl<T> Vector<T> {
         self.length -= 1;
 
         unsafe {
-            let end = self.ptr.as_ptr().add(self.length);
+            let end = self.buf.ptr().add(self.length);
             // ptr::read copia os bits para fora da memória e transfere a propriedade pro destino.
             Some(ptr::read(end))
         }
     }
 
-    fn grow(&mut self) {
-        // malloc / realloc em rust exigem alinhamento explícito.
-        // o layout guarda o size + alignment. se errarmos o alinhamento, é undefined behaviour
-        // (terra do diabo). considere parecido com posix_memalign em vez do malloc
-        let (new_capacity, new_layout) = match self.capacity == 0 {
-            true => (1, Layout::array::<T>(1).unwrap()),
-            false => {
-                let new_capacity = self.capacity * 2;
-                let new_layout = Layout::array::<T>(new_capacity).unwrap();
-                (new_capacity, new_layout)
+    pub fn reserve(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve(additional))
+    }
+
+    pub fn reserve_exact(&mut self, additional: usize) {
+        handle_reserve(self.try_reserve_exact(additional))
+    }
+
+    /// Garante espaço para pelo menos mais `additional` elementos.
+    /// Assim como no `push`, a capacidade cresce de acordo com a [`GrowthPolicy`] para manter
+    /// o custo amortizado baixo.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve(self.length, additional)
+    }
+
+    /// Igual ao `try_reserve`, mas aloca exatamente o necessário, ignorando a [`GrowthPolicy`].
+    /// Bom quando já sabemos que o vetor não vai crescer mais.
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.buf.try_reserve_exact(self.length, additional)
+    }
+
+    /// Devolve pro alocador toda a memória que não está sendo usada.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to(0)
+    }
+
+    /// Diminui a capacidade pra no mínimo `max(len, min_capacity)`.
+    /// Se a capacidade já for menor que isso, não faz nada.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        handle_reserve(self.buf.try_shrink_to(self.length.max(min_capacity)))
+    }
+}
+
+impl<T, A: RawAlloc> Vector<T, A> {
+    /// Insere `element` na posição `index`, empurrando todos os elementos depois dele
+    /// uma posição pra direita. O(n).
+    pub fn insert(&mut self, index: usize, element: T) {
+        let length = self.length;
+        assert!(
+            index <= length,
+            "insertion index (is {index}) should be <= len (is {length})"
+        );
+
+        if self.length == self.capacity() {
+            self.reserve(1);
+        }
+
+        unsafe {
+            let slot = self.buf.ptr().add(index);
+            // `ptr::copy` é o memmove: funciona mesmo quando origem e destino se sobrepõem.
+            ptr::copy(slot, slot.add(1), length - index);
+            ptr::write(slot, element);
+        }
+
+        self.length += 1;
+    }
+
+    /// Remove e retorna o elemento em `index`, puxando todo o resto uma posição pra esquerda. O(n).
+    pub fn remove(&mut self, index: usize) -> T {
+        let length = self.length;
+        assert!(
+            index < length,
+            "removal index (is {index}) should be < len (is {length})"
+        );
+
+        self.length -= 1;
+
+        unsafe {
+            let slot = self.buf.ptr().add(index);
+            let element = ptr::read(slot);
+            ptr::copy(slot.add(1), slot, length - index - 1);
+
+            element
+        }
+    }
+
+    /// Remove o elemento em `index` trocando ele de lugar com o último. O(1), mas não preserva a ordem.
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        let length = self.length;
+        assert!(
+            index < length,
+            "swap_remove index (is {index}) should be < len (is {length})"
+        );
+
+        self.length -= 1;
+
+        unsafe {
+            let slot = self.buf.ptr().add(index);
+            let element = ptr::read(slot);
+            // se `index` já era o último, origem e destino são o mesmo lugar e não tem problema.
+            ptr::copy(self.buf.ptr().add(self.length), slot, 1);
+
+            element
+        }
+    }
+
+    /// Mantém só os primeiros `length` elementos, dropando o resto. A capacidade não muda.
+    pub fn truncate(&mut self, length: usize) {
+        if length >= self.length {
+            return;
+        }
+
+        let remaining = self.length - length;
+
+        unsafe {
+            let tail = ptr::slice_from_raw_parts_mut(self.buf.ptr().add(length), remaining);
+            // atualizamos o tamanho *antes* de dropar: se o drop de algum elemento der panic,
+            // o vetor já não enxerga mais esses elementos e não vai tentar dropar eles de novo.
+            self.length = length;
+            ptr::drop_in_place(tail);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Remove os elementos em `range` e retorna um iterador que entrega eles por valor.
+    ///
+    /// Os elementos que o iterador não consumir são dropados junto com ele. Se o iterador for
+    /// esquecido com `mem::forget`, o vetor fica só com os elementos antes de `range`
+    /// (o resto vaza, mas nada é dropado duas vezes).
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A> {
+        let length = self.length;
+        let Range { start, end } = slice_range(range, length);
+
+        // a partir daqui o vetor "esquece" tudo do `start` pra frente. quem cuida desses
+        // elementos agora é o `Drain`, que coloca a cauda de volta no lugar quando for dropado.
+        self.length = start;
+
+        Drain {
+            vector: self,
+            index: start,
+            end,
+            tail_start: end,
+            tail_length: length - end,
+        }
+    }
+}
+
+impl<T, A: RawAlloc> Vector<T, A> {
+    /// Mantém só os elementos em que `keep` devolve `true`, preservando a ordem. O(n).
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        self.retain_mut(|element| keep(element))
+    }
+
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, mut keep: F) {
+        let mut gap = Gap::new(self);
+
+        // uma passada só: quem fica é copiado pra trás, tapando o buraco dos removidos.
+        while gap.read < gap.original {
+            let current = unsafe { &mut *gap.vector.buf.ptr().add(gap.read) };
+
+            if keep(current) {
+                gap.keep_current();
+            } else {
+                gap.drop_current();
             }
-        };
+        }
+    }
 
+    /// Remove elementos consecutivos repetidos. Num vetor ordenado, remove todas as repetições.
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|current, previous| current == previous)
+    }
+
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|current, previous| key(current) == key(previous))
+    }
+
+    /// Remove elementos consecutivos para os quais `same_bucket(atual, anterior)` devolve `true`.
+    /// O "anterior" é sempre o último elemento que ficou no vetor. O(n).
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same_bucket: F) {
+        if self.length <= 1 {
+            return;
+        }
+
+        let mut gap = Gap::new(self);
+        // o primeiro elemento sempre fica
+        gap.keep_current();
+
+        while gap.read < gap.original {
+            let base = gap.vector.buf.ptr();
+            let (current, previous) =
+                unsafe { (&mut *base.add(gap.read), &mut *base.add(gap.write - 1)) };
+
+            if same_bucket(current, previous) {
+                gap.drop_current();
+            } else {
+                gap.keep_current();
+            }
+        }
+    }
+
+    /// Divide o vetor em dois: `self` fica com `[0, at)` e o retorno com `[at, len)`.
+    pub fn split_off(&mut self, at: usize) -> Self
+    where
+        A: Clone,
+    {
+        let length = self.length;
         assert!(
-            new_layout.size() <= isize::MAX as usize,
-            "Allocation too large"
+            at <= length,
+            "`at` split index (is {at}) should be <= len (is {length})"
         );
 
-        let new_ptr = match self.capacity == 0 {
-            true => unsafe { alloc::alloc(new_layout) },
-            false => {
-                let old_layout = Layout::array::<T>(self.capacity).unwrap();
-                let old_ptr = self.ptr.as_ptr() as *mut u8;
-                unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+        let count = length - at;
+        let mut other = Vector::with_capacity_in(count, self.buf.allocator().clone())
+            .with_growth_policy(self.growth_policy());
+
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.ptr().add(at), other.buf.ptr(), count);
+        }
+
+        // os elementos mudaram de dono: nenhum dos dois vai dropar algo que não é seu.
+        self.length = at;
+        other.length = count;
+
+        other
+    }
+
+    /// Move todos os elementos de `other` pro final desse vetor, deixando `other` vazio.
+    pub fn append<B: RawAlloc>(&mut self, other: &mut Vector<T, B>) {
+        let count = other.length;
+        self.reserve(count);
+
+        unsafe {
+            ptr::copy_nonoverlapping(other.buf.ptr(), self.buf.ptr().add(self.length), count);
+        }
+
+        other.length = 0;
+        self.length += count;
+    }
+
+    pub fn extend_from_slice(&mut self, other: &[T])
+    where
+        T: Clone,
+    {
+        self.reserve(other.len());
+
+        // como já reservamos, nenhum desses `push` realoca. se um `clone` der panic,
+        // os que já foram escritos continuam contados no `length`.
+        for element in other {
+            self.push(element.clone());
+        }
+    }
+
+    /// Clona os elementos em `range` pro final do próprio vetor.
+    pub fn extend_from_within<R: RangeBounds<usize>>(&mut self, range: R)
+    where
+        T: Clone,
+    {
+        let range = slice_range(range, self.length);
+        self.reserve(range.len());
+
+        for index in range {
+            // não dá pra usar `self[index]` e `self.push` ao mesmo tempo (o borrow checker não deixa),
+            // mas como reservamos antes, o buffer não muda de lugar e o ponteiro continua válido.
+            unsafe {
+                let element = (*self.buf.ptr().add(index)).clone();
+                ptr::write(self.buf.ptr().add(self.length), element);
             }
-        };
 
-        // tratamento simples para out of memory
-        self.ptr = NonNull::new(new_ptr as *mut T).expect("Memory allocation failed");
-        self.capacity = new_capacity
+            self.length += 1;
+        }
+    }
+
+    /// Substitui os elementos em `range` pelos de `replace_with`, devolvendo um iterador com
+    /// os elementos removidos. A troca de verdade acontece quando o iterador é dropado.
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, I::IntoIter, A>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+}
+
+// guard usado pelos algoritmos que removem elementos "no meio" numa passada só (`retain`, `dedup`).
+// os elementos em `[0, write)` são os que ficaram, `[write, read)` é o buraco e
+// `[read, original)` ainda não foi processado.
+//
+// enquanto ele existe o vetor acha que está vazio. se algo der panic no meio do caminho,
+// o drop do guard fecha o buraco e o vetor fica num estado válido (sem double drop).
+struct Gap<'a, T, A: RawAlloc> {
+    vector: &'a mut Vector<T, A>,
+    read: usize,
+    write: usize,
+    original: usize,
+}
+
+impl<'a, T, A: RawAlloc> Gap<'a, T, A> {
+    fn new(vector: &'a mut Vector<T, A>) -> Self {
+        let original = vector.length;
+        vector.length = 0;
+
+        Self {
+            vector,
+            read: 0,
+            write: 0,
+            original,
+        }
+    }
+
+    fn keep_current(&mut self) {
+        if self.read != self.write {
+            unsafe {
+                let base = self.vector.buf.ptr();
+                ptr::copy_nonoverlapping(base.add(self.read), base.add(self.write), 1);
+            }
+        }
+
+        self.read += 1;
+        self.write += 1;
     }
+
+    fn drop_current(&mut self) {
+        // avançamos antes de dropar: se o drop der panic, esse elemento já conta como removido.
+        self.read += 1;
+        unsafe { ptr::drop_in_place(self.vector.buf.ptr().add(self.read - 1)) }
+    }
+}
+
+impl<T, A: RawAlloc> Drop for Gap<'_, T, A> {
+    fn drop(&mut self) {
+        let remaining = self.original - self.read;
+
+        unsafe {
+            let base = self.vector.buf.ptr();
+            ptr::copy(base.add(self.read), base.add(self.write), remaining);
+        }
+
+        self.vector.length = self.write + remaining;
+    }
+}
+
+// transforma qualquer range (`..`, `a..`, `..=b`, ...) num `Range` concreto, checando os limites.
+fn slice_range<R: RangeBounds<usize>>(range: R, length: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("range start overflow"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("range end overflow"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => length,
+    };
+
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+    assert!(
+        end <= length,
+        "range end index {end} out of range for slice of length {length}"
+    );
+
+    start..end
 }
 
 // implementar deref faz o papel do `decay` em c++.
 // permite tratar &Vector como &[T] (slice).
 // o slice em rust é um fat pointer (ponteiro + tamanho) nativo da linguagem.
-impl<T> Deref for Vector<T> {
+impl<T, A: RawAlloc> Deref for Vector<T, A> {
     type Target = [T];
 
     fn deref(&self) -> &Self::Target {
-        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.length) }
+        unsafe { std::slice::from_raw_parts(self.buf.ptr(), self.length) }
     }
 }
 
-impl<T> DerefMut for Vector<T> {
+impl<T, A: RawAlloc> DerefMut for Vector<T, A> {
     fn deref_mut(&mut self) -> &mut [T] {
-        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.length) }
+        unsafe { std::slice::from_raw_parts_mut(self.buf.ptr(), self.length) }
     }
 }
 
 // como vector é uma estrutura que é alocada na heap e fazemos isso manualmente
 // ao implementarmos drop, ao sair do escopo da função, a linguagem saberá como liberar essa
 // estrutura da memória corretamente.
-impl<T> Drop for Vector<T> {
+impl<T, A: RawAlloc> Drop for Vector<T, A> {
     fn drop(&mut self) {
-        if self.capacity != 0 {
-            // iteramos todos os items e os removemos da memória com o pop
-            // que usa o ptr::read
-            while let Some(_) = self.pop() {}
+        // iteramos todos os items e os removemos da memória com o pop
+        // que usa o ptr::read. mesmo ZSTs podem ter um `Drop` com efeitos, então
+        // isso acontece independente de termos alocado algo ou não.
+        while self.pop().is_some() {}
+
+        // a memória em si é liberada logo depois, pelo `Drop` do `RawBuffer`.
+    }
+}
 
-            let layout = Layout::array::<T>(self.capacity).unwrap();
-            unsafe { alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout) }
+impl<T: Clone, A: RawAlloc + Clone> Clone for Vector<T, A> {
+    fn clone(&self) -> Self {
+        // uma única alocação com o tamanho exato. se algum `T::clone` der panic no meio,
+        // o vetor novo é dropado normalmente e só os elementos que já tinham sido clonados
+        // são dropados (o `length` sempre reflete exatamente o que foi escrito).
+        let mut vector = Vector::with_capacity_in(self.length, self.buf.allocator().clone())
+            .with_growth_policy(self.growth_policy());
+        for element in self.iter() {
+            vector.push(element.clone());
         }
+
+        vector
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T: fmt::Debug, A: RawAlloc> fmt::Debug for Vector<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
 
-    #[test]
-    fn test_push_pop_basic() {
-        let mut v = Vector::new();
-        v.push(1);
-        v.push(2);
-        v.push(3);
+impl<T, A: RawAlloc + Default> Default for Vector<T, A> {
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
 
-        assert_eq!(v.len(), 3);
+// todas as comparações e o hash delegam pro slice, assim um `Vector` se comporta
+// exatamente como um `[T]` com o mesmo conteúdo.
+impl<T: PartialEq<U>, U, A: RawAlloc, B: RawAlloc> PartialEq<Vector<U, B>> for Vector<T, A> {
+    fn eq(&self, other: &Vector<U, B>) -> bool {
+        self[..] == other[..]
+    }
+}
 
-        // verifica ordem lifo (last in, first out) no pop
-        assert_eq!(v.pop(), Some(3));
-        assert_eq!(v.pop(), Some(2));
-        assert_eq!(v.len(), 1);
+impl<T: PartialEq<U>, U, A: RawAlloc> PartialEq<[U]> for Vector<T, A> {
+    fn eq(&self, other: &[U]) -> bool {
+        self[..] == *other
+    }
+}
 
-        v.push(4);
-        assert_eq!(v.pop(), Some(4));
-        assert_eq!(v.pop(), Some(1));
-        assert_eq!(v.pop(), None); // underflow check
+impl<T: PartialEq<U>, U, A: RawAlloc> PartialEq<&[U]> for Vector<T, A> {
+    fn eq(&self, other: &&[U]) -> bool {
+        self[..] == **other
     }
+}
 
-    #[test]
-    fn test_growth() {
-        let mut vec = Vector::new();
+impl<T: PartialEq<U>, U, A: RawAlloc, const N: usize> PartialEq<[U; N]> for Vector<T, A> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        self[..] == other[..]
+    }
+}
 
-        for n in 0..10 {
-            vec.push(n);
+impl<T: Eq, A: RawAlloc> Eq for Vector<T, A> {}
+
+impl<T: PartialOrd, A: RawAlloc> PartialOrd for Vector<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self[..].partial_cmp(&other[..])
+    }
+}
+
+impl<T: Ord, A: RawAlloc> Ord for Vector<T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self[..].cmp(&other[..])
+    }
+}
+
+impl<T: Hash, A: RawAlloc> Hash for Vector<T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self[..].hash(state)
+    }
+}
+
+impl<T, A: RawAlloc> IntoIterator for Vector<T, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(mut self) -> IntoIter<T, A> {
+        let end = self.length;
+        // o vetor passa a achar que está vazio: quando ele for dropado dentro do `IntoIter`,
+        // só o buffer é liberado, e os elementos ficam por conta do iterador.
+        self.length = 0;
+
+        IntoIter {
+            buffer: self,
+            index: 0,
+            end,
         }
+    }
+}
 
-        assert_eq!(vec.len(), 10);
-        assert!(vec.capacity() >= 10);
+impl<'a, T, A: RawAlloc> IntoIterator for &'a Vector<T, A> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
 
-        // isso testa nossa implementação de deref / deref_mut :D
-        assert_eq!(vec[0], 0);
-        assert_eq!(vec[5], 5);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
 
-    #[test]
-    fn test_iterators() {
-        // uma das grandes magias de rust são seus iteradores (fortemente inspirado em linguagens
-        // funcionais, como ocaml)
-        // então, como implementamos deref que faz nosso vetor ser tratado como um slice,
-        // deveriamos ser agraciados com essa magia tbm >:)
-        //
+impl<'a, T, A: RawAlloc> IntoIterator for &'a mut Vector<T, A> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
 
-        let mut vec = Vector::new();
-        vec.push("Hello");
-        vec.push("World");
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
 
-        // basicamente podemos transformar nossa implementação em um iterator nativo da linguagem
-        // (isso é mt lindo)
-        // e então usarmos ferramentas da linguagem e coletar isso como um vetor padrão de rust.
-        let lengths: Vec<usize> = vec.iter().map(|string| string.len()).collect();
-        assert_eq!(lengths, vec![5, 5])
+/// Iterador que consome um [`Vector`] e entrega seus elementos por valor.
+pub struct IntoIter<T, A: RawAlloc = Global> {
+    // reaproveitamos o próprio vetor (com `length == 0`) pra cuidar do buffer.
+    buffer: Vector<T, A>,
+    // elementos que ainda não foram entregues: `index..end`
+    index: usize,
+    end: usize,
+}
+
+impl<T, A: RawAlloc> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == self.end {
+            return None;
+        }
+
+        let element = unsafe { ptr::read(self.buffer.buf.ptr().add(self.index)) };
+        self.index += 1;
+
+        Some(element)
     }
 
-    #[test]
-    fn test_complex_types_strings() {
-        let mut v = Vector::new();
-        // força múltiplos reallocs com strings (que têm sua própria heap)
-        for i in 0..20 {
-            v.push(format!("String {}", i));
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, A: RawAlloc> DoubleEndedIterator for IntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.index == self.end {
+            return None;
         }
 
-        assert_eq!(v.len(), 20);
-        assert_eq!(v[0], "String 0");
-        assert_eq!(v[19], "String 19");
+        self.end -= 1;
+        Some(unsafe { ptr::read(self.buffer.buf.ptr().add(self.end)) })
+    }
+}
 
-        // se o realloc fosse feito errado (ex: shallow copy sem cuidado),
+impl<T, A: RawAlloc> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: RawAlloc> Drop for IntoIter<T, A> {
+    fn drop(&mut self) {
+        let remaining = self.end - self.index;
+        let first = self.index;
+        self.index = self.end;
+
+        // dropa o que sobrou. o buffer em si é liberado logo depois pelo `Drop` do `Vector`,
+        // mesmo que algum desses drops dê panic.
+        unsafe {
+            let pending = self.buffer.buf.ptr().add(first);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(pending, remaining));
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Vector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vector = Vector::new();
+        vector.extend(iter);
+
+        vector
+    }
+}
+
+impl<T, A: RawAlloc> Extend<T> for Vector<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        // o limite inferior do `size_hint` é garantido, então dá pra reservar tudo de uma vez
+        // e evitar vários reallocs no meio do caminho.
+        let (lower, _) = iter.size_hint();
+        self.reserve(lower);
+
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<'a, T: Copy + 'a, A: RawAlloc> Extend<&'a T> for Vector<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+/// Iterador criado por [`Vector::drain`].
+pub struct Drain<'a, T, A: RawAlloc = Global> {
+    vector: &'a mut Vector<T, A>,
+    // próximos elementos a serem entregues: `index..end`
+    index: usize,
+    end: usize,
+    // elementos depois do range, que precisam voltar pro vetor no final
+    tail_start: usize,
+    tail_length: usize,
+}
+
+impl<T, A: RawAlloc> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index == self.end {
+            return None;
+        }
+
+        let element = unsafe { ptr::read(self.vector.buf.ptr().add(self.index)) };
+        self.index += 1;
+
+        Some(element)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T, A: RawAlloc> DoubleEndedIterator for Drain<'_, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.index == self.end {
+            return None;
+        }
+
+        self.end -= 1;
+        Some(unsafe { ptr::read(self.vector.buf.ptr().add(self.end)) })
+    }
+}
+
+impl<T, A: RawAlloc> ExactSizeIterator for Drain<'_, T, A> {}
+
+impl<T, A: RawAlloc> Drop for Drain<'_, T, A> {
+    fn drop(&mut self) {
+        // esse guard move a cauda de volta mesmo se o drop de algum elemento der panic.
+        // sem ele, um panic no meio do caminho deixaria a cauda perdida pra sempre.
+        struct TailGuard<'r, 'a, T, A: RawAlloc>(&'r mut Drain<'a, T, A>);
+
+        impl<T, A: RawAlloc> Drop for TailGuard<'_, '_, T, A> {
+            fn drop(&mut self) {
+                let drain = &mut *self.0;
+                let vector = &mut *drain.vector;
+                let start = vector.length;
+
+                unsafe {
+                    let base = vector.buf.ptr();
+                    ptr::copy(
+                        base.add(drain.tail_start),
+                        base.add(start),
+                        drain.tail_length,
+                    );
+                }
+
+                vector.length = start + drain.tail_length;
+            }
+        }
+
+        let remaining = self.end - self.index;
+        let first = self.index;
+        // marcamos tudo como consumido antes de dropar, pelo mesmo motivo do `truncate`.
+        self.index = self.end;
+
+        let guard = TailGuard(self);
+        unsafe {
+            let pending = guard.0.vector.buf.ptr().add(first);
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(pending, remaining));
+        }
+    }
+}
+
+/// Iterador criado por [`Vector::splice`].
+pub struct Splice<'a, I: Iterator, A: RawAlloc = Global> {
+    drain: Drain<'a, I::Item, A>,
+    replace_with: I,
+}
+
+impl<I: Iterator, A: RawAlloc> Iterator for Splice<'_, I, A> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl<I: Iterator, A: RawAlloc> DoubleEndedIterator for Splice<'_, I, A> {
+    fn next_back(&mut self) -> Option<I::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl<I: Iterator, A: RawAlloc> ExactSizeIterator for Splice<'_, I, A> {}
+
+impl<I: Iterator, A: RawAlloc> Drop for Splice<'_, I, A> {
+    fn drop(&mut self) {
+        // primeiro dropamos o que sobrou do range. depois disso o vetor tem
+        // `[0, len)` + buraco + cauda em `[tail_start, tail_start + tail_length)`.
+        self.drain.by_ref().for_each(drop);
+
+        let drain = &mut self.drain;
+
+        // 1. preenchemos o buraco deixado pelo range. o `length` vai crescendo junto,
+        // então se o iterador der panic o `Drain` ainda sabe pra onde mover a cauda.
+        while drain.vector.length < drain.tail_start {
+            let Some(element) = self.replace_with.next() else {
+                // acabaram os substitutos: o drop do `Drain` fecha o resto do buraco.
+                return;
+            };
+
+            unsafe { ptr::write(drain.vector.buf.ptr().add(drain.vector.length), element) };
+            drain.vector.length += 1;
+        }
+
+        // 2. sobraram substitutos: guardamos eles num vetor temporário pra saber quantos são,
+        // e movemos a cauda uma única vez pra abrir espaço pra todos.
+        let rest: Vector<I::Item> = self.replace_with.by_ref().collect();
+        if rest.length == 0 {
+            return;
+        }
+
+        // com `length == tail_start`, isso garante espaço pra cauda + os novos elementos.
+        // o realloc copia o bloco inteiro, então a cauda (que está depois do `length`) vem junto.
+        drain.vector.reserve(drain.tail_length + rest.length);
+
+        unsafe {
+            let base = drain.vector.buf.ptr();
+            let new_tail_start = drain.tail_start + rest.length;
+            ptr::copy(
+                base.add(drain.tail_start),
+                base.add(new_tail_start),
+                drain.tail_length,
+            );
+            drain.tail_start = new_tail_start;
+        }
+
+        for element in rest {
+            unsafe { ptr::write(drain.vector.buf.ptr().add(drain.vector.length), element) };
+            drain.vector.length += 1;
+        }
+    }
+}
+
+/// Cria um [`Vector`] com os elementos passados, igual ao `vec!` da `std`.
+///
+/// `vector![a, b, c]` coloca os elementos na ordem, e `vector![x; n]` repete `x` `n` vezes.
+#[macro_export]
+macro_rules! vector {
+    () => {
+        $crate::vector::Vector::new()
+    };
+    ($element:expr; $n:expr) => {
+        ::std::iter::repeat_n($element, $n).collect::<$crate::vector::Vector<_>>()
+    };
+    ($($element:expr),+ $(,)?) => {
+        <$crate::vector::Vector<_> as ::std::iter::FromIterator<_>>::from_iter([$($element),+])
+    };
+}
+
+pub(crate) use vector;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::allocator::{Bump, Counting};
+
+    #[test]
+    fn test_push_pop_basic() {
+        let mut v = Vector::new();
+        v.push(1);
+        v.push(2);
+        v.push(3);
+
+        assert_eq!(v.len(), 3);
+
+        // verifica ordem lifo (last in, first out) no pop
+        assert_eq!(v.pop(), Some(3));
+        assert_eq!(v.pop(), Some(2));
+        assert_eq!(v.len(), 1);
+
+        v.push(4);
+        assert_eq!(v.pop(), Some(4));
+        assert_eq!(v.pop(), Some(1));
+        assert_eq!(v.pop(), None); // underflow check
+    }
+
+    #[test]
+    fn test_growth() {
+        let mut vec = Vector::new();
+
+        for n in 0..10 {
+            vec.push(n);
+        }
+
+        assert_eq!(vec.len(), 10);
+        assert!(vec.capacity() >= 10);
+
+        // isso testa nossa implementação de deref / deref_mut :D
+        assert_eq!(vec[0], 0);
+        assert_eq!(vec[5], 5);
+    }
+
+    #[test]
+    fn test_iterators() {
+        // uma das grandes magias de rust são seus iteradores (fortemente inspirado em linguagens
+        // funcionais, como ocaml)
+        // então, como implementamos deref que faz nosso vetor ser tratado como um slice,
+        // deveriamos ser agraciados com essa magia tbm >:)
+        //
+
+        let mut vec = Vector::new();
+        vec.push("Hello");
+        vec.push("World");
+
+        // basicamente podemos transformar nossa implementação em um iterator nativo da linguagem
+        // (isso é mt lindo)
+        // e então usarmos ferramentas da linguagem e coletar isso como um vetor padrão de rust.
+        let lengths: Vec<usize> = vec.iter().map(|string| string.len()).collect();
+        assert_eq!(lengths, vec![5, 5])
+    }
+
+    #[test]
+    fn test_complex_types_strings() {
+        let mut v = Vector::new();
+        // força múltiplos reallocs com strings (que têm sua própria heap)
+        for i in 0..20 {
+            v.push(format!("String {}", i));
+        }
+
+        assert_eq!(v.len(), 20);
+        assert_eq!(v[0], "String 0");
+        assert_eq!(v[19], "String 19");
+
+        // se o realloc fosse feito errado (ex: shallow copy sem cuidado),
         // ao acessar essas strings teríamos segfault (double free ou use-after-free).
     }
+
+    #[test]
+    fn test_zero_sized_unit() {
+        let mut v = Vector::new();
+        // nada deveria ser alocado, a capacidade é "infinita"
+        assert_eq!(v.capacity(), usize::MAX);
+
+        for _ in 0..1000 {
+            v.push(());
+        }
+
+        assert_eq!(v.len(), 1000);
+        assert_eq!(v.capacity(), usize::MAX);
+        assert_eq!(v[999], ());
+        assert_eq!(v.iter().count(), 1000);
+
+        assert_eq!(v.pop(), Some(()));
+        assert_eq!(v.len(), 999);
+    }
+
+    #[test]
+    fn test_zero_sized_empty_struct() {
+        #[derive(Debug, PartialEq)]
+        struct Marker;
+
+        let mut v = Vector::new();
+        v.push(Marker);
+        v.push(Marker);
+
+        assert_eq!(v.len(), 2);
+        assert_eq!(&v[..], &[Marker, Marker]);
+        assert_eq!(v.pop(), Some(Marker));
+        assert_eq!(v.pop(), Some(Marker));
+        assert_eq!(v.pop(), None);
+    }
+
+    #[test]
+    fn test_zero_sized_drop() {
+        use std::cell::Cell;
+
+        thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        // um ZST que tem efeito colateral no drop. se o `Drop` do vetor pular os ZSTs
+        // (já que não tem nada pra desalocar), esse contador vai denunciar.
+        struct Token;
+
+        impl Drop for Token {
+            fn drop(&mut self) {
+                DROPS.with(|drops| drops.set(drops.get() + 1));
+            }
+        }
+
+        {
+            let mut v = Vector::new();
+            for _ in 0..10 {
+                v.push(Token);
+            }
+
+            drop(v.pop());
+            assert_eq!(DROPS.with(Cell::get), 1);
+        }
+
+        assert_eq!(DROPS.with(Cell::get), 10);
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let mut v = Vector::with_capacity(10);
+        assert_eq!(v.len(), 0);
+        assert_eq!(v.capacity(), 10);
+
+        for i in 0..10 {
+            v.push(i);
+        }
+
+        // nada de realloc até aqui
+        assert_eq!(v.capacity(), 10);
+    }
+
+    #[test]
+    fn test_try_push_and_reserve() {
+        let mut v = Vector::new();
+        assert_eq!(v.try_push(String::from("a")), Ok(()));
+        assert_eq!(v.try_reserve(10), Ok(()));
+        assert!(v.capacity() >= 11);
+        assert_eq!(v[0], "a");
+    }
+
+    #[test]
+    fn test_try_reserve_capacity_overflow() {
+        let mut v: Vector<u64> = Vector::new();
+        v.push(1);
+
+        // len + additional não cabe em usize
+        assert_eq!(
+            v.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        // cabe em usize, mas passa de isize::MAX bytes
+        assert_eq!(
+            v.try_reserve(usize::MAX / 8),
+            Err(TryReserveError::CapacityOverflow)
+        );
+
+        // o vetor continua utilizável depois do erro
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0], 1);
+
+        let mut zst = Vector::new();
+        zst.push(());
+        assert_eq!(
+            zst.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow)
+        );
+        assert!(Vector::<u8>::try_with_capacity(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_try_reserve_alloc_error() {
+        let mut v: Vector<u8> = Vector::new();
+        v.push(42);
+
+        // um layout válido (< isize::MAX), mas que nenhum alocador vai conseguir entregar
+        let result = v.try_reserve(isize::MAX as usize / 2);
+        assert!(matches!(result, Err(TryReserveError::AllocError { .. })));
+
+        assert_eq!(v.len(), 1);
+        assert_eq!(v[0], 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity overflow")]
+    fn test_with_capacity_panics_on_overflow() {
+        Vector::<u32>::with_capacity(usize::MAX);
+    }
+
+    #[test]
+    fn test_bump_allocator() {
+        let arena = Bump::with_capacity(1024);
+        let mut a = Vector::new_in(&arena);
+        let mut b = Vector::new_in(&arena);
+
+        for i in 0..16u32 {
+            a.push(i);
+            b.push(i as u64 * 2);
+        }
+
+        assert_eq!(a[15], 15);
+        assert_eq!(b[15], 30);
+        assert!(arena.used() <= arena.capacity());
+    }
+
+    #[test]
+    fn test_bump_allocator_exhausted() {
+        // 16 bytes dão pra exatamente 4 u32
+        let arena = Bump::with_capacity(16);
+        let mut v = Vector::with_capacity_in(4, &arena);
+
+        for i in 0..4u32 {
+            assert_eq!(v.try_push(i), Ok(()));
+        }
+
+        assert!(matches!(
+            v.try_push(4),
+            Err(TryReserveError::AllocError { .. })
+        ));
+        assert_eq!(&v[..], &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_counting_allocator() {
+        let counting = Counting::new(Global);
+
+        {
+            let mut v = Vector::new_in(&counting);
+            for i in 0..8 {
+                v.push(format!("String {}", i));
+            }
+
+            // 1 -> 2 -> 4 -> 8
+            assert_eq!(counting.allocations(), 1);
+            assert_eq!(counting.reallocations(), 3);
+            assert_eq!(counting.live(), 1);
+        }
+
+        assert_eq!(counting.deallocations(), 1);
+        assert_eq!(counting.live(), 0);
+
+        // ZSTs nunca tocam no alocador
+        let mut zst = Vector::new_in(&counting);
+        zst.push(());
+        assert_eq!(counting.allocations(), 1);
+    }
+
+    #[test]
+    fn test_insert_remove() {
+        let mut v = Vector::new();
+        for i in 0..5 {
+            v.push(format!("String {}", i));
+        }
+
+        v.insert(0, String::from("first"));
+        v.insert(3, String::from("middle"));
+        v.insert(v.len(), String::from("last"));
+        assert_eq!(
+            &v[..],
+            &[
+                "first", "String 0", "String 1", "middle", "String 2", "String 3", "String 4",
+                "last"
+            ]
+        );
+
+        assert_eq!(v.remove(3), "middle");
+        assert_eq!(v.remove(0), "first");
+        assert_eq!(v.remove(v.len() - 1), "last");
+        assert_eq!(
+            &v[..],
+            &["String 0", "String 1", "String 2", "String 3", "String 4"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+    fn test_insert_out_of_bounds() {
+        let mut v = Vector::new();
+        v.push(1);
+        v.insert(2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "removal index (is 0) should be < len (is 0)")]
+    fn test_remove_out_of_bounds() {
+        let mut v: Vector<i32> = Vector::new();
+        v.remove(0);
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        let mut v = Vector::new();
+        for i in 0..4 {
+            v.push(format!("String {}", i));
+        }
+
+        assert_eq!(v.swap_remove(0), "String 0");
+        assert_eq!(&v[..], &["String 3", "String 1", "String 2"]);
+
+        // remover o último não troca nada
+        assert_eq!(v.swap_remove(2), "String 2");
+        assert_eq!(&v[..], &["String 3", "String 1"]);
+    }
+
+    #[test]
+    fn test_truncate_and_clear() {
+        let mut v = Vector::new();
+        for i in 0..10 {
+            v.push(format!("String {}", i));
+        }
+        let capacity = v.capacity();
+
+        v.truncate(20);
+        assert_eq!(v.len(), 10);
+
+        v.truncate(3);
+        assert_eq!(&v[..], &["String 0", "String 1", "String 2"]);
+        assert_eq!(v.capacity(), capacity);
+
+        v.clear();
+        assert_eq!(v.len(), 0);
+        assert_eq!(v.capacity(), capacity);
+
+        v.push(String::from("again"));
+        assert_eq!(v[0], "again");
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut v = Vector::new();
+        for i in 0..6 {
+            v.push(format!("String {}", i));
+        }
+
+        let drained: Vec<String> = v.drain(1..4).collect();
+        assert_eq!(drained, ["String 1", "String 2", "String 3"]);
+        assert_eq!(&v[..], &["String 0", "String 4", "String 5"]);
+
+        // de trás pra frente e parando no meio: o resto é dropado junto com o iterador
+        let mut drain = v.drain(..);
+        assert_eq!(drain.len(), 3);
+        assert_eq!(drain.next_back().as_deref(), Some("String 5"));
+        drop(drain);
+        assert_eq!(v.len(), 0);
+
+        v.push(String::from("a"));
+        v.push(String::from("b"));
+        assert_eq!(v.drain(..=0).next().as_deref(), Some("a"));
+        assert_eq!(v.drain(1..).count(), 0);
+        assert_eq!(&v[..], &["b"]);
+    }
+
+    #[test]
+    fn test_drain_forget_leaks_gracefully() {
+        let mut v = Vector::new();
+        for i in 0..5 {
+            v.push(format!("String {}", i));
+        }
+
+        let mut drain = v.drain(2..4);
+        assert_eq!(drain.next().as_deref(), Some("String 2"));
+        std::mem::forget(drain);
+
+        // sem o drop do `Drain` a cauda não volta, mas o vetor continua consistente
+        assert_eq!(&v[..], &["String 0", "String 1"]);
+        v.push(String::from("String 5"));
+        assert_eq!(v[2], "String 5");
+    }
+
+    #[test]
+    fn test_drain_panic_safety() {
+        use std::{
+            cell::Cell,
+            panic::{self, AssertUnwindSafe},
+        };
+
+        thread_local! {
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        struct Bomb(bool);
+
+        impl Drop for Bomb {
+            fn drop(&mut self) {
+                DROPS.with(|drops| drops.set(drops.get() + 1));
+                if self.0 {
+                    panic!("boom");
+                }
+            }
+        }
+
+        let mut v = Vector::new();
+        for i in 0..6 {
+            v.push(Bomb(i == 2));
+        }
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| drop(v.drain(1..4))));
+        assert!(result.is_err());
+
+        // todos os 3 elementos drenados foram dropados (inclusive os depois da bomba)
+        // e a cauda voltou pro lugar
+        assert_eq!(DROPS.with(Cell::get), 3);
+        assert_eq!(v.len(), 3);
+
+        drop(v);
+        assert_eq!(DROPS.with(Cell::get), 6);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let mut v = Vector::new();
+        for i in 0..5 {
+            v.push(format!("String {}", i));
+        }
+
+        let mut iter = v.into_iter();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.next().as_deref(), Some("String 0"));
+        assert_eq!(iter.next_back().as_deref(), Some("String 4"));
+        assert_eq!(iter.len(), 3);
+
+        let rest: Vec<String> = iter.collect();
+        assert_eq!(rest, ["String 1", "String 2", "String 3"]);
+    }
+
+    #[test]
+    fn test_into_iter_frees_buffer_once() {
+        let counting = Counting::new(Global);
+        let mut v = Vector::new_in(&counting);
+        for i in 0..10 {
+            v.push(format!("String {}", i));
+        }
+
+        // consumimos só uma parte: o resto precisa ser dropado e o buffer liberado uma vez só
+        let mut iter = v.into_iter();
+        iter.next();
+        iter.next_back();
+        drop(iter);
+
+        assert_eq!(counting.allocations(), 1);
+        assert_eq!(counting.deallocations(), 1);
+    }
+
+    #[test]
+    fn test_borrowing_into_iter() {
+        let mut v = Vector::new();
+        v.push(1);
+        v.push(2);
+
+        for n in &mut v {
+            *n *= 10;
+        }
+
+        let mut sum = 0;
+        for n in &v {
+            sum += n;
+        }
+        assert_eq!(sum, 30);
+    }
+
+    #[test]
+    fn test_collect_and_extend() {
+        let mut v: Vector<String> = (0..3).map(|i| format!("String {}", i)).collect();
+        assert_eq!(v.len(), 3);
+        // o `size_hint` exato do range faz a gente alocar uma vez só
+        assert_eq!(v.capacity(), 3);
+
+        v.extend(vec![String::from("a"), String::from("b")]);
+        assert_eq!(&v[..], &["String 0", "String 1", "String 2", "a", "b"]);
+
+        let mut numbers: Vector<i32> = Vector::new();
+        numbers.extend(&[1, 2, 3]);
+        numbers.extend([4, 5].iter());
+        assert_eq!(&numbers[..], &[1, 2, 3, 4, 5]);
+
+        let evens: Vector<i32> = numbers.into_iter().filter(|n| n % 2 == 0).collect();
+        assert_eq!(&evens[..], &[2, 4]);
+    }
+
+    #[test]
+    fn test_macro() {
+        let empty: Vector<i32> = vector![];
+        assert_eq!(empty.len(), 0);
+
+        let v = vector![1, 2, 3];
+        assert_eq!(v, [1, 2, 3]);
+        assert_eq!(v.capacity(), 3);
+
+        let repeated = vector![String::from("a"); 3];
+        assert_eq!(repeated, ["a", "a", "a"]);
+        assert_eq!(vector![String::new(); 0].len(), 0);
+    }
+
+    #[test]
+    fn test_clone() {
+        let counting = Counting::new(Global);
+        let mut v = Vector::new_in(&counting);
+        for i in 0..5 {
+            v.push(format!("String {}", i));
+        }
+
+        let allocations = counting.allocations();
+        let mut cloned = v.clone();
+        assert_eq!(counting.allocations(), allocations + 1);
+        assert_eq!(counting.reallocations(), 3);
+        assert_eq!(cloned.capacity(), 5);
+        assert_eq!(cloned, v);
+
+        // os dois são independentes
+        cloned[0].push('!');
+        assert_eq!(v[0], "String 0");
+        assert_eq!(cloned[0], "String 0!");
+    }
+
+    #[test]
+    fn test_clone_panic_safety() {
+        use std::{
+            cell::Cell,
+            panic::{self, AssertUnwindSafe},
+        };
+
+        thread_local! {
+            static CLONES: Cell<usize> = const { Cell::new(0) };
+            static DROPS: Cell<usize> = const { Cell::new(0) };
+        }
+
+        // o terceiro clone explode
+        struct Fragile;
+
+        impl Clone for Fragile {
+            fn clone(&self) -> Self {
+                CLONES.with(|clones| clones.set(clones.get() + 1));
+                if CLONES.with(Cell::get) == 3 {
+                    panic!("clone failed");
+                }
+
+                Fragile
+            }
+        }
+
+        impl Drop for Fragile {
+            fn drop(&mut self) {
+                DROPS.with(|drops| drops.set(drops.get() + 1));
+            }
+        }
+
+        let v = vector![Fragile, Fragile, Fragile, Fragile];
+        let result = panic::catch_unwind(AssertUnwindSafe(|| v.clone()));
+        assert!(result.is_err());
+
+        // os dois clones que deram certo foram dropados, nenhum a mais
+        assert_eq!(DROPS.with(Cell::get), 2);
+        assert_eq!(v.len(), 4);
+    }
+
+    #[test]
+    fn test_debug_and_default() {
+        let v = vector!["a", "b"];
+        assert_eq!(format!("{:?}", v), r#"["a", "b"]"#);
+
+        let empty: Vector<i32> = Vector::default();
+        assert_eq!(format!("{:?}", empty), "[]");
+    }
+
+    #[test]
+    fn test_comparisons_and_hash() {
+        use std::collections::{HashMap, hash_map::DefaultHasher};
+
+        let a = vector![1, 2, 3];
+        let b = vector![1, 2, 4];
+        let c = vector![1, 2];
+
+        assert_eq!(a, a.clone());
+        assert_ne!(a, b);
+        assert!(a < b);
+        assert!(c < a);
+        assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+        assert_eq!(a, &[1, 2, 3][..]);
+
+        let hash = |v: &Vector<i32>| {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&a.clone()));
+
+        let mut map = HashMap::new();
+        map.insert(a, "a");
+        map.insert(c, "c");
+        assert_eq!(map.get(&vector![1, 2, 3]), Some(&"a"));
+    }
+
+    #[test]
+    fn test_reserve() {
+        let mut v: Vector<i32> = Vector::new();
+        v.reserve(10);
+        assert_eq!(v.capacity(), 10);
+
+        v.push(1);
+        // ainda cabe, nada muda
+        v.reserve(9);
+        assert_eq!(v.capacity(), 10);
+
+        // `reserve` respeita a política de crescimento: 10 * 2
+        v.reserve(10);
+        assert_eq!(v.capacity(), 20);
+
+        // `reserve_exact` pede exatamente o que falta
+        v.reserve_exact(30);
+        assert_eq!(v.capacity(), 31);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let counting = Counting::new(Global);
+        let mut v = Vector::with_capacity_in(100, &counting);
+        for i in 0..10 {
+            v.push(format!("String {}", i));
+        }
+
+        v.shrink_to(50);
+        assert_eq!(v.capacity(), 50);
+
+        // não aumenta a capacidade
+        v.shrink_to(80);
+        assert_eq!(v.capacity(), 50);
+
+        // nunca fica menor que o tamanho
+        v.shrink_to(2);
+        assert_eq!(v.capacity(), 10);
+        assert_eq!(v[9], "String 9");
+
+        v.clear();
+        v.shrink_to_fit();
+        assert_eq!(v.capacity(), 0);
+        assert_eq!(counting.live(), 0);
+
+        // depois de encolher pra zero o vetor continua utilizável
+        v.push(String::from("again"));
+        assert_eq!(v.capacity(), 1);
+        assert_eq!(counting.live(), 1);
+    }
+
+    #[test]
+    fn test_shrink_zero_sized() {
+        let mut v = vector![(), (), ()];
+        v.shrink_to_fit();
+        assert_eq!(v.capacity(), usize::MAX);
+        assert_eq!(v.len(), 3);
+    }
+
+    // quantos reallocs são necessários pra empurrar `n` elementos com a política dada
+    fn reallocs_for(growth: GrowthPolicy, n: usize) -> (usize, usize) {
+        let counting = Counting::new(Global);
+        let mut v = Vector::new_in(&counting).with_growth_policy(growth);
+        for i in 0..n {
+            v.push(i as u8);
+        }
+
+        (counting.reallocations(), v.capacity())
+    }
+
+    #[test]
+    fn test_growth_policies() {
+        // 1, 2, 4, ..., 128
+        assert_eq!(reallocs_for(GrowthPolicy::Doubling, 100), (7, 128));
+        // 1, 2, 3, 4, 6, 9, 13, 19, 28, 42, 63, 94, 141
+        assert_eq!(reallocs_for(GrowthPolicy::OneAndHalf, 100), (12, 141));
+        // 10, 20, ..., 100
+        assert_eq!(reallocs_for(GrowthPolicy::Increment(10), 100), (9, 100));
+        // 8, 16, ..., 128
+        assert_eq!(reallocs_for(GrowthPolicy::SizeAware, 100), (4, 128));
+
+        // incremento zero vira "cresce só o necessário"
+        assert_eq!(reallocs_for(GrowthPolicy::Increment(0), 10), (9, 10));
+    }
+
+    #[test]
+    fn test_size_aware_minimum() {
+        let mut small = Vector::new().with_growth_policy(GrowthPolicy::SizeAware);
+        small.push(1u8);
+        assert_eq!(small.capacity(), 8);
+
+        let mut medium = Vector::new().with_growth_policy(GrowthPolicy::SizeAware);
+        medium.push(1u64);
+        assert_eq!(medium.capacity(), 4);
+
+        let mut large = Vector::new().with_growth_policy(GrowthPolicy::SizeAware);
+        large.push([0u8; 2048]);
+        assert_eq!(large.capacity(), 1);
+        assert_eq!(large.growth_policy(), GrowthPolicy::SizeAware);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut v: Vector<String> = (0..10).map(|i| format!("String {}", i)).collect();
+        v.retain(|s| s.ends_with(['0', '3', '9']));
+        assert_eq!(v, ["String 0", "String 3", "String 9"]);
+
+        let mut numbers = vector![1, 2, 3, 4, 5];
+        numbers.retain_mut(|n| {
+            *n *= 10;
+            *n != 30
+        });
+        assert_eq!(numbers, [10, 20, 40, 50]);
+
+        numbers.retain(|_| false);
+        assert_eq!(numbers.len(), 0);
+    }
+
+    #[test]
+    fn test_retain_panic_safety() {
+        use std::panic::{self, AssertUnwindSafe};
+
+        let mut v: Vector<String> = (0..6).map(|i| format!("String {}", i)).collect();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            v.retain(|s| {
+                if s == "String 3" {
+                    panic!("predicate failed");
+                }
+
+                s != "String 1"
+            })
+        }));
+
+        assert!(result.is_err());
+        // o que já tinha sido processado foi filtrado e o resto ficou intacto
+        assert_eq!(
+            v,
+            ["String 0", "String 2", "String 3", "String 4", "String 5"]
+        );
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut v = vector!["a", "a", "b", "c", "c", "c", "a"];
+        v.dedup();
+        assert_eq!(v, ["a", "b", "c", "a"]);
+
+        let mut v = vector![10, 11, 20, 21, 22, 30];
+        v.dedup_by_key(|n| *n / 10);
+        assert_eq!(v, [10, 20, 30]);
+
+        let mut v: Vector<String> = ["foo", "FOO", "bar", "Bar", "baz"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        v.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(v, ["foo", "bar", "baz"]);
+
+        let mut single = vector![1];
+        single.dedup();
+        assert_eq!(single, [1]);
+    }
+
+    #[test]
+    fn test_split_off_and_append() {
+        let mut v: Vector<String> = (0..5).map(|i| format!("String {}", i)).collect();
+        let mut tail = v.split_off(2);
+
+        assert_eq!(v, ["String 0", "String 1"]);
+        assert_eq!(tail, ["String 2", "String 3", "String 4"]);
+
+        let empty = tail.split_off(3);
+        assert_eq!(empty.len(), 0);
+
+        v.append(&mut tail);
+        assert_eq!(v.len(), 5);
+        assert_eq!(v[4], "String 4");
+        assert_eq!(tail.len(), 0);
+
+        // `other` continua utilizável depois do append
+        tail.push(String::from("again"));
+        assert_eq!(tail, ["again"]);
+    }
+
+    #[test]
+    fn test_extend_from_slice_and_within() {
+        let mut v = vector![String::from("a")];
+        v.extend_from_slice(&[String::from("b"), String::from("c")]);
+        assert_eq!(v, ["a", "b", "c"]);
+
+        v.extend_from_within(1..);
+        assert_eq!(v, ["a", "b", "c", "b", "c"]);
+
+        v.extend_from_within(..=0);
+        assert_eq!(v, ["a", "b", "c", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_splice() {
+        // substituindo por menos elementos
+        let mut v = vector![1, 2, 3, 4, 5];
+        let removed: Vec<i32> = v.splice(1..4, [10]).collect();
+        assert_eq!(removed, [2, 3, 4]);
+        assert_eq!(v, [1, 10, 5]);
+
+        // mesma quantidade
+        let mut v = vector![1, 2, 3];
+        drop(v.splice(..2, [7, 8]));
+        assert_eq!(v, [7, 8, 3]);
+
+        // mais elementos do que o range: a cauda precisa andar
+        let mut v: Vector<String> = (0..4).map(|i| format!("String {}", i)).collect();
+        let inserted = (0..5).map(|i| format!("new {}", i));
+        let removed: Vec<String> = v.splice(1..2, inserted).collect();
+        assert_eq!(removed, ["String 1"]);
+        assert_eq!(
+            v,
+            [
+                "String 0", "new 0", "new 1", "new 2", "new 3", "new 4", "String 2", "String 3"
+            ]
+        );
+
+        // range vazio funciona como inserção
+        let mut v = vector![1, 4];
+        drop(v.splice(1..1, [2, 3]));
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
 }